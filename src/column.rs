@@ -0,0 +1,470 @@
+use std::fmt;
+
+use crate::{CsvError, CsvFile, RowRef};
+
+/**
+ * Column Values
+ *
+ * Cells for a new column.
+ */
+pub enum ColumnValues<'a> {
+    /// One cell per row.
+    Values(Vec<String>),
+    /// The same cell for every row.
+    Default(String),
+    /// A cell computed from each row.
+    With(Box<dyn Fn(RowRef<'_>) -> String + 'a>),
+}
+
+impl<'a> ColumnValues<'a> {
+    /**
+     * Values
+     *
+     * One cell per row.
+     */
+    pub fn values<I, S>(values: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::Values(values.into_iter().map(Into::into).collect())
+    }
+
+    /**
+     * Default
+     *
+     * The same cell for every row.
+     */
+    pub fn default(value: &str) -> Self {
+        Self::Default(value.to_string())
+    }
+
+    /**
+     * With
+     *
+     * A cell computed from each row.
+     */
+    pub fn with<F: Fn(RowRef<'_>) -> String + 'a>(f: F) -> Self {
+        Self::With(Box::new(f))
+    }
+}
+
+impl fmt::Debug for ColumnValues<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Values(values) => f.debug_tuple("Values").field(values).finish(),
+            Self::Default(value) => f.debug_tuple("Default").field(value).finish(),
+            Self::With(_) => f.write_str("With(..)"),
+        }
+    }
+}
+
+impl CsvFile {
+    /**
+     * Column Index
+     *
+     * Position of the head `name`, or an unknown column error.
+     */
+    pub(crate) fn column_index(&self, name: &str) -> Result<usize, CsvError> {
+        self.head_pos(name)
+            .ok_or_else(|| CsvError::UnknownColumn(name.to_string()))
+    }
+
+    /**
+     * Col
+     *
+     * Borrow the cells of the named column. Missing cells read as empty.
+     */
+    pub fn col(&self, name: &str) -> Option<impl Iterator<Item = &str> + '_> {
+        let index = self.head_pos(name)?;
+
+        Some(
+            self.rows
+                .iter()
+                .map(move |row| row.get(index).map_or("", String::as_str)),
+        )
+    }
+
+    /**
+     * Col Mut
+     *
+     * Mutably borrow the cells of the named column. Rows too short to have
+     * the cell are padded with empty cells first.
+     */
+    pub fn col_mut(&mut self, name: &str) -> Option<impl Iterator<Item = &mut String> + '_> {
+        let index = self.head_pos(name)?;
+
+        Some(self.rows.iter_mut().map(move |row| {
+            if row.len() <= index {
+                row.resize(index + 1, String::new());
+            }
+
+            &mut row[index]
+        }))
+    }
+
+    /**
+     * Map Column
+     *
+     * Replace every cell of the named column with `f(cell)`.
+     */
+    pub fn map_column<F>(&mut self, name: &str, mut f: F) -> Result<(), CsvError>
+    where
+        F: FnMut(&str) -> String,
+    {
+        let cells = self
+            .col_mut(name)
+            .ok_or_else(|| CsvError::UnknownColumn(name.to_string()))?;
+
+        for cell in cells {
+            *cell = f(cell);
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Add Column
+     *
+     * Append a column with the given cells.
+     */
+    pub fn add_column(&mut self, name: &str, values: ColumnValues<'_>) -> Result<(), CsvError> {
+        self.insert_column_at(self.heads.len(), name, values)
+    }
+
+    /**
+     * Insert Column At
+     *
+     * Insert a column with the given cells at `position`.
+     */
+    pub fn insert_column_at(
+        &mut self,
+        position: usize,
+        name: &str,
+        values: ColumnValues<'_>,
+    ) -> Result<(), CsvError> {
+        if position > self.heads.len() {
+            return Err(CsvError::OutOfBounds {
+                index: position,
+                len: self.heads.len() + 1,
+            });
+        }
+
+        if self.head_pos(name).is_some() {
+            return Err(CsvError::DuplicateColumn(name.to_string()));
+        }
+
+        let cells = match values {
+            ColumnValues::Values(values) if values.len() != self.rows.len() => {
+                return Err(CsvError::ShapeMismatch {
+                    expected: self.rows.len(),
+                    found: values.len(),
+                })
+            }
+            ColumnValues::Values(values) => values,
+            ColumnValues::Default(value) => vec![value; self.rows.len()],
+            ColumnValues::With(f) => self.iter().map(f).collect(),
+        };
+
+        self.heads.insert(position, name.to_string());
+        for (row, cell) in self.rows.iter_mut().zip(cells) {
+            if row.len() < position {
+                row.resize(position, String::new());
+            }
+
+            row.insert(position, cell);
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Rename Column
+     *
+     * Rename the head `from` to `to`.
+     */
+    pub fn rename_column(&mut self, from: &str, to: &str) -> Result<(), CsvError> {
+        let position = self.column_index(from)?;
+
+        if from != to && self.head_pos(to).is_some() {
+            return Err(CsvError::DuplicateColumn(to.to_string()));
+        }
+
+        self.heads[position] = to.to_string();
+
+        Ok(())
+    }
+
+    /**
+     * Drop Columns
+     *
+     * Remove the named columns. Nothing is removed if any name is unknown.
+     */
+    pub fn drop_columns(&mut self, names: &[&str]) -> Result<(), CsvError> {
+        let mut positions = names
+            .iter()
+            .map(|name| self.column_index(name))
+            .collect::<Result<Vec<usize>, CsvError>>()?;
+
+        positions.sort_unstable();
+        positions.dedup();
+
+        // Remove from the back so earlier positions stay valid.
+        for &position in positions.iter().rev() {
+            self.heads.remove(position);
+
+            for row in self.rows.iter_mut() {
+                if position < row.len() {
+                    row.remove(position);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Reorder Columns
+     *
+     * Put the named columns first, in the given order. Other columns follow
+     * in their current order.
+     */
+    pub fn reorder_columns(&mut self, names: &[&str]) -> Result<(), CsvError> {
+        let mut order = Vec::with_capacity(self.heads.len());
+
+        for name in names {
+            let position = self.column_index(name)?;
+
+            if order.contains(&position) {
+                return Err(CsvError::DuplicateColumn(name.to_string()));
+            }
+
+            order.push(position);
+        }
+
+        for position in 0..self.heads.len() {
+            if !order.contains(&position) {
+                order.push(position);
+            }
+        }
+
+        self.heads = order.iter().map(|&i| self.heads[i].clone()).collect();
+        for row in self.rows.iter_mut() {
+            // Cells past the heads keep their place at the end.
+            let extra = row.split_off(row.len().min(order.len()));
+            let mut cells = order
+                .iter()
+                .map(|&i| row.get(i).cloned().unwrap_or_default())
+                .collect::<Vec<String>>();
+
+            cells.extend(extra);
+            *row = cells;
+        }
+
+        Ok(())
+    }
+
+    /**
+     * Move Column
+     *
+     * Move the named column to `position`.
+     */
+    pub fn move_column(&mut self, name: &str, position: usize) -> Result<(), CsvError> {
+        let from = self.column_index(name)?;
+
+        if position >= self.heads.len() {
+            return Err(CsvError::OutOfBounds {
+                index: position,
+                len: self.heads.len(),
+            });
+        }
+
+        let head = self.heads.remove(from);
+        self.heads.insert(position, head);
+
+        for row in self.rows.iter_mut() {
+            let width = from.max(position) + 1;
+            if row.len() < width {
+                row.resize(width, String::new());
+            }
+
+            let cell = row.remove(from);
+            row.insert(position, cell);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShapePolicy;
+
+    fn file(heads: &[&str], rows: &[&[&str]]) -> CsvFile {
+        let mut file = CsvFile::new();
+        for head in heads {
+            file.push_head(head);
+        }
+        for row in rows {
+            file.push_row(row).unwrap();
+        }
+        file
+    }
+
+    fn sample() -> CsvFile {
+        file(&["a", "b", "c"], &[&["1", "2", "3"], &["4", "5", "6"]])
+    }
+
+    #[test]
+    fn add_column_from_each_kind_of_values() {
+        let mut file = sample();
+
+        file.add_column("d", ColumnValues::values(["x", "y"]))
+            .unwrap();
+        file.add_column("e", ColumnValues::default("-")).unwrap();
+        file.add_column(
+            "f",
+            ColumnValues::with(|row| format!("{}{}", row.get("a").unwrap(), row.get("d").unwrap())),
+        )
+        .unwrap();
+
+        assert_eq!(file.heads(), &["a", "b", "c", "d", "e", "f"]);
+        assert_eq!(
+            file.rows(),
+            &[
+                vec!["1", "2", "3", "x", "-", "1x"],
+                vec!["4", "5", "6", "y", "-", "4y"],
+            ]
+        );
+    }
+
+    #[test]
+    fn insert_column_checks_its_input() {
+        let mut file = sample();
+
+        file.insert_column_at(1, "x", ColumnValues::default("0"))
+            .unwrap();
+        assert_eq!(file.heads(), &["a", "x", "b", "c"]);
+        assert_eq!(file.rows()[0], ["1", "0", "2", "3"]);
+
+        assert!(matches!(
+            file.insert_column_at(9, "y", ColumnValues::default("")),
+            Err(CsvError::OutOfBounds { index: 9, len: 5 })
+        ));
+        assert!(matches!(
+            file.add_column("a", ColumnValues::default("")),
+            Err(CsvError::DuplicateColumn(name)) if name == "a"
+        ));
+        assert!(matches!(
+            file.add_column("y", ColumnValues::values(["only one"])),
+            Err(CsvError::ShapeMismatch {
+                expected: 2,
+                found: 1
+            })
+        ));
+        assert_eq!(file.heads().len(), 4);
+    }
+
+    #[test]
+    fn rename_column() {
+        let mut file = sample();
+
+        file.rename_column("b", "bee").unwrap();
+        file.rename_column("a", "a").unwrap();
+        assert_eq!(file.heads(), &["a", "bee", "c"]);
+
+        assert!(matches!(
+            file.rename_column("a", "c"),
+            Err(CsvError::DuplicateColumn(_))
+        ));
+        assert!(matches!(
+            file.rename_column("b", "x"),
+            Err(CsvError::UnknownColumn(_))
+        ));
+    }
+
+    #[test]
+    fn drop_columns_is_all_or_nothing() {
+        let mut file = sample();
+
+        assert!(matches!(
+            file.drop_columns(&["a", "missing"]),
+            Err(CsvError::UnknownColumn(_))
+        ));
+        assert_eq!(file, sample());
+
+        file.drop_columns(&["c", "a", "c"]).unwrap();
+        assert_eq!(file.heads(), &["b"]);
+        assert_eq!(file.rows(), &[vec!["2"], vec!["5"]]);
+    }
+
+    #[test]
+    fn reorder_columns_puts_names_first() {
+        let mut file = sample();
+
+        file.reorder_columns(&["c", "a"]).unwrap();
+        assert_eq!(file.heads(), &["c", "a", "b"]);
+        assert_eq!(file.rows(), &[vec!["3", "1", "2"], vec!["6", "4", "5"]]);
+
+        assert!(matches!(
+            file.reorder_columns(&["a", "a"]),
+            Err(CsvError::DuplicateColumn(_))
+        ));
+    }
+
+    #[test]
+    fn move_column_both_ways() {
+        let mut file = sample();
+
+        file.move_column("a", 2).unwrap();
+        assert_eq!(file.heads(), &["b", "c", "a"]);
+        assert_eq!(file.rows()[0], ["2", "3", "1"]);
+
+        file.move_column("a", 0).unwrap();
+        assert_eq!(file, sample());
+
+        assert!(matches!(
+            file.move_column("a", 3),
+            Err(CsvError::OutOfBounds { index: 3, len: 3 })
+        ));
+    }
+
+    #[test]
+    fn col_reads_missing_cells_as_empty() {
+        let mut file = sample();
+        file.set_shape_policy(ShapePolicy::Flexible);
+        file.push_row(&["7"]).unwrap();
+
+        assert_eq!(file.col("b").unwrap().collect::<Vec<_>>(), ["2", "5", ""]);
+        assert!(file.col("missing").is_none());
+    }
+
+    #[test]
+    fn col_mut_pads_short_rows() {
+        let mut file = sample();
+        file.set_shape_policy(ShapePolicy::Flexible);
+        file.push_row(&["7"]).unwrap();
+
+        for cell in file.col_mut("c").unwrap() {
+            cell.push('!');
+        }
+
+        assert_eq!(file.rows()[0], ["1", "2", "3!"]);
+        assert_eq!(file.rows()[2], ["7", "", "!"]);
+        assert!(file.col_mut("missing").is_none());
+    }
+
+    #[test]
+    fn map_column() {
+        let mut file = sample();
+
+        file.map_column("a", |cell| format!("<{cell}>")).unwrap();
+        assert_eq!(file.col("a").unwrap().collect::<Vec<_>>(), ["<1>", "<4>"]);
+
+        assert!(matches!(
+            file.map_column("missing", str::to_string),
+            Err(CsvError::UnknownColumn(_))
+        ));
+    }
+}
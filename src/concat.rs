@@ -0,0 +1,193 @@
+use crate::{CsvError, CsvFile};
+
+/**
+ * Concat Options
+ *
+ * How files with different heads are combined.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConcatOptions {
+    /// Fail unless every file has the same set of heads, in any order.
+    pub strict: bool,
+    /// Cell used for columns a file doesn't have.
+    pub fill: String,
+}
+
+impl CsvFile {
+    /**
+     * Concat
+     *
+     * Stack the rows of every file, aligning columns by head name. Heads
+     * appear in the order they are first seen; missing cells are empty.
+     */
+    pub fn concat(files: &[CsvFile]) -> Result<CsvFile, CsvError> {
+        Self::concat_with(files, &ConcatOptions::default())
+    }
+
+    /**
+     * Concat With
+     *
+     * Stack the rows of every file with the given options.
+     */
+    pub fn concat_with(files: &[CsvFile], options: &ConcatOptions) -> Result<CsvFile, CsvError> {
+        let mut file = CsvFile::new();
+
+        if let Some(first) = files.first() {
+            file.shape = first.shape.clone();
+        }
+
+        for other in files {
+            file.extend_with(other, options)?;
+        }
+
+        Ok(file)
+    }
+
+    /**
+     * Extend From
+     *
+     * Append the rows of `other`, aligning columns by head name. Heads only
+     * `other` has are added, with empty cells for the existing rows.
+     */
+    pub fn extend_from(&mut self, other: &CsvFile) -> Result<(), CsvError> {
+        self.extend_with(other, &ConcatOptions::default())
+    }
+
+    /**
+     * Extend With
+     *
+     * Append the rows of `other` with the given options.
+     */
+    pub fn extend_with(
+        &mut self,
+        other: &CsvFile,
+        options: &ConcatOptions,
+    ) -> Result<(), CsvError> {
+        // A new file takes the heads of the first file added.
+        if self.heads.is_empty() && self.rows.is_empty() {
+            self.heads = other.heads.clone();
+        }
+
+        // Files without heads are stacked by position.
+        if self.heads.is_empty() && other.heads.is_empty() {
+            self.rows.extend(other.rows.iter().cloned());
+            return Ok(());
+        }
+
+        let same_set = self.heads.len() == other.heads.len()
+            && other.heads.iter().all(|head| self.heads.contains(head));
+        if (options.strict || self.heads.is_empty() || other.heads.is_empty()) && !same_set {
+            return Err(CsvError::HeadMismatch {
+                expected: self.heads.clone(),
+                found: other.heads.clone(),
+            });
+        }
+
+        let width = self.heads.len();
+        for head in other.heads.iter() {
+            if self.head_pos(head).is_none() {
+                self.heads.push(head.clone());
+            }
+        }
+
+        // Flexible rows may be wider than the heads. Their extra cells move
+        // past the new columns rather than being read as them.
+        if self.heads.len() > width {
+            for row in self.rows.iter_mut() {
+                let extra = row.split_off(row.len().min(width));
+                row.resize(self.heads.len(), options.fill.clone());
+                row.extend(extra);
+            }
+        }
+
+        // Where each of our heads is in `other`.
+        let positions = self
+            .heads
+            .iter()
+            .map(|head| other.head_pos(head))
+            .collect::<Vec<Option<usize>>>();
+
+        for row in other.rows.iter() {
+            self.rows.push(
+                positions
+                    .iter()
+                    .map(|position| match position {
+                        Some(position) => row
+                            .get(*position)
+                            .cloned()
+                            .unwrap_or_else(|| options.fill.clone()),
+                        None => options.fill.clone(),
+                    })
+                    .collect(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShapePolicy;
+
+    fn file(heads: &[&str], rows: &[&[&str]]) -> CsvFile {
+        let mut file = CsvFile::new();
+        file.set_shape_policy(ShapePolicy::Flexible);
+        for head in heads {
+            file.push_head(head);
+        }
+        for row in rows {
+            file.push_row(row).unwrap();
+        }
+        file
+    }
+
+    #[test]
+    fn concat_aligns_columns_by_name() {
+        let a = file(&["x", "y"], &[&["1", "2"]]);
+        let b = file(&["y", "z"], &[&["3", "4"]]);
+
+        let both = CsvFile::concat(&[a, b]).unwrap();
+        assert_eq!(both.heads(), &["x", "y", "z"]);
+        assert_eq!(both.rows(), &[vec!["1", "2", ""], vec!["", "3", "4"]]);
+    }
+
+    #[test]
+    fn strict_concat_rejects_other_heads() {
+        let a = file(&["x"], &[]);
+        let b = file(&["y"], &[]);
+        let options = ConcatOptions {
+            strict: true,
+            ..ConcatOptions::default()
+        };
+
+        assert!(matches!(
+            CsvFile::concat_with(&[a, b], &options),
+            Err(CsvError::HeadMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn extra_cells_move_past_new_columns() {
+        let mut a = file(&["x"], &[&["1", "extra"]]);
+        let b = file(&["x", "y"], &[&["2", "3"]]);
+
+        a.extend_from(&b).unwrap();
+        assert_eq!(a.heads(), &["x", "y"]);
+        assert_eq!(a.rows(), &[vec!["1", "", "extra"], vec!["2", "3"]]);
+    }
+
+    #[test]
+    fn short_rows_are_filled() {
+        let mut a = file(&["x", "y"], &[&["1"]]);
+        let b = file(&["y", "x", "z"], &[&["2"]]);
+        let options = ConcatOptions {
+            fill: "?".into(),
+            ..ConcatOptions::default()
+        };
+
+        a.extend_with(&b, &options).unwrap();
+        assert_eq!(a.rows(), &[vec!["1", "?", "?"], vec!["?", "2", "?"]]);
+    }
+}
@@ -0,0 +1,423 @@
+use std::error::Error;
+use std::fmt;
+
+use serde::de::value::BorrowedStrDeserializer;
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::{forward_to_deserialize_any, Deserialize};
+
+use crate::{CsvError, CsvFile};
+
+impl CsvFile {
+    /**
+     * Deserialize
+     *
+     * Deserialize every row into `T`, matching struct fields by head name.
+     * Without heads, rows are deserialized by position as sequences.
+     */
+    pub fn deserialize<'de, T: Deserialize<'de>>(&'de self) -> Result<Vec<T>, CsvError> {
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| {
+                T::deserialize(RowDeserializer {
+                    heads: &self.heads,
+                    row,
+                })
+                .map_err(|err| CsvError::Deserialize {
+                    row: index,
+                    field: err.field,
+                    message: err.message,
+                })
+            })
+            .collect()
+    }
+}
+
+/**
+ * De Error
+ *
+ * Deserialize error, tagged with the head of the cell when known.
+ */
+#[derive(Debug)]
+struct DeError {
+    field: Option<String>,
+    message: String,
+}
+
+impl fmt::Display for DeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl Error for DeError {}
+
+impl de::Error for DeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self {
+            field: None,
+            message: msg.to_string(),
+        }
+    }
+}
+
+/**
+ * Row Deserializer
+ *
+ * Deserializes a row as a map of head to cell, or a sequence of cells.
+ */
+struct RowDeserializer<'de> {
+    heads: &'de [String],
+    row: &'de [String],
+}
+
+impl<'de> Deserializer<'de> for RowDeserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        if self.heads.is_empty() {
+            self.deserialize_seq(visitor)
+        } else {
+            self.deserialize_map(visitor)
+        }
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_map(RowMap {
+            cells: self.heads.iter().zip(self.row.iter()),
+            value: None,
+        })
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_seq(RowSeq {
+            cells: self.row.iter(),
+        })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct enum identifier ignored_any
+    }
+}
+
+/**
+ * Row Map
+ *
+ * Head and cell pairs of a row.
+ */
+struct RowMap<'de, I> {
+    cells: I,
+    value: Option<(&'de str, &'de str)>,
+}
+
+impl<'de, I> MapAccess<'de> for RowMap<'de, I>
+where
+    I: Iterator<Item = (&'de String, &'de String)>,
+{
+    type Error = DeError;
+
+    fn next_key_seed<K: DeserializeSeed<'de>>(
+        &mut self,
+        seed: K,
+    ) -> Result<Option<K::Value>, DeError> {
+        let Some((head, cell)) = self.cells.next() else {
+            return Ok(None);
+        };
+
+        self.value = Some((head, cell));
+        seed.deserialize(BorrowedStrDeserializer::new(head))
+            .map(Some)
+    }
+
+    fn next_value_seed<V: DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, DeError> {
+        let (head, cell) = self
+            .value
+            .take()
+            .ok_or_else(|| de::Error::custom("value requested before key"))?;
+
+        seed.deserialize(CellDeserializer {
+            value: cell,
+            field: Some(head),
+        })
+        .map_err(|mut err| {
+            err.field.get_or_insert_with(|| head.to_string());
+            err
+        })
+    }
+}
+
+/**
+ * Row Seq
+ *
+ * Cells of a row by position.
+ */
+struct RowSeq<I> {
+    cells: I,
+}
+
+impl<'de, I> SeqAccess<'de> for RowSeq<I>
+where
+    I: Iterator<Item = &'de String>,
+{
+    type Error = DeError;
+
+    fn next_element_seed<T: DeserializeSeed<'de>>(
+        &mut self,
+        seed: T,
+    ) -> Result<Option<T::Value>, DeError> {
+        let Some(cell) = self.cells.next() else {
+            return Ok(None);
+        };
+
+        seed.deserialize(CellDeserializer {
+            value: cell,
+            field: None,
+        })
+        .map(Some)
+    }
+}
+
+/**
+ * Cell Deserializer
+ *
+ * Parses a single cell into the type the visitor asks for.
+ */
+struct CellDeserializer<'de> {
+    value: &'de str,
+    field: Option<&'de str>,
+}
+
+impl CellDeserializer<'_> {
+    fn error(&self, message: String) -> DeError {
+        DeError {
+            field: self.field.map(str::to_string),
+            message,
+        }
+    }
+
+    fn parse<T: std::str::FromStr>(&self, kind: &str) -> Result<T, DeError> {
+        self.value
+            .trim()
+            .parse()
+            .map_err(|_| self.error(format!("invalid {kind}: {:?}", self.value)))
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident: $ty:ty,)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+                visitor.$visit(self.parse::<$ty>(stringify!($ty))?)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for CellDeserializer<'de> {
+    type Error = DeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_borrowed_str(self.value)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool: bool,
+        deserialize_i8 => visit_i8: i8,
+        deserialize_i16 => visit_i16: i16,
+        deserialize_i32 => visit_i32: i32,
+        deserialize_i64 => visit_i64: i64,
+        deserialize_i128 => visit_i128: i128,
+        deserialize_u8 => visit_u8: u8,
+        deserialize_u16 => visit_u16: u16,
+        deserialize_u32 => visit_u32: u32,
+        deserialize_u64 => visit_u64: u64,
+        deserialize_u128 => visit_u128: u128,
+        deserialize_f32 => visit_f32: f32,
+        deserialize_f64 => visit_f64: f64,
+    }
+
+    fn deserialize_char<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        let mut chars = self.value.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => visitor.visit_char(c),
+            _ => Err(self.error(format!("invalid char: {:?}", self.value))),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_borrowed_bytes(self.value.as_bytes())
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        if self.value.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DeError> {
+        visitor.visit_enum(BorrowedStrDeserializer::new(self.value))
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DeError> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        str string identifier seq tuple tuple_struct map struct
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use crate::{CsvError, CsvFile, ReadOptions};
+
+    fn read(input: &str, has_headers: bool) -> CsvFile {
+        let options = ReadOptions {
+            has_headers,
+            ..ReadOptions::default()
+        };
+
+        CsvFile::from_reader(input.as_bytes(), &options).unwrap()
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Status {
+        Active,
+        Retired,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Person {
+        name: String,
+        age: Option<u32>,
+        status: Status,
+    }
+
+    #[test]
+    fn fields_match_heads_in_any_order() {
+        let file = read("status,age,name\nActive,30,Ann\nRetired,,Bob\n", true);
+
+        assert_eq!(
+            file.deserialize::<Person>().unwrap(),
+            [
+                Person {
+                    name: "Ann".into(),
+                    age: Some(30),
+                    status: Status::Active,
+                },
+                Person {
+                    name: "Bob".into(),
+                    age: None,
+                    status: Status::Retired,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rows_without_heads_are_tuples() {
+        let file = read("Ann,30\nBob,41\n", false);
+
+        assert_eq!(
+            file.deserialize::<(String, u8)>().unwrap(),
+            [("Ann".to_string(), 30), ("Bob".to_string(), 41)]
+        );
+    }
+
+    #[test]
+    fn bad_cells_name_their_row_and_field() {
+        let file = read("name,age,status\nAnn,30,Active\nBob,old,Active\n", true);
+
+        match file.deserialize::<Person>() {
+            Err(CsvError::Deserialize {
+                row,
+                field,
+                message,
+            }) => {
+                assert_eq!(row, 1);
+                assert_eq!(field.as_deref(), Some("age"));
+                assert!(message.contains("\"old\""), "{message}");
+            }
+            other => panic!("expected a Deserialize error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn unknown_variants_fail() {
+        let file = read("name,age,status\nAnn,30,Away\n", true);
+
+        assert!(matches!(
+            file.deserialize::<Person>(),
+            Err(CsvError::Deserialize { row: 0, .. })
+        ));
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+
+use crate::{CsvError, CsvFile};
+
+/**
+ * Keep
+ *
+ * Which row of a duplicate group survives.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Keep {
+    #[default]
+    First,
+    Last,
+    /// Drop every row that has a duplicate.
+    None,
+}
+
+impl CsvFile {
+    /**
+     * Dedup
+     *
+     * Remove rows equal to an earlier row. Returns how many were removed.
+     */
+    pub fn dedup(&mut self) -> usize {
+        // Whole-row keys can't name an unknown column.
+        self.dedup_by(&[], Keep::First).unwrap_or_default()
+    }
+
+    /**
+     * Dedup By
+     *
+     * Remove rows whose cells in the named columns repeat, keeping the row
+     * chosen by `keep`. No columns compares whole rows. Returns how many
+     * rows were removed.
+     */
+    pub fn dedup_by(&mut self, columns: &[&str], keep: Keep) -> Result<usize, CsvError> {
+        let mut kept = vec![true; self.rows.len()];
+
+        for group in self.duplicates(columns)? {
+            let survivor = match keep {
+                Keep::First => group.first().copied(),
+                Keep::Last => group.last().copied(),
+                Keep::None => None,
+            };
+
+            for index in group {
+                kept[index] = Some(index) == survivor;
+            }
+        }
+
+        let before = self.rows.len();
+        let mut index = 0;
+        self.rows.retain(|_| {
+            index += 1;
+            kept[index - 1]
+        });
+
+        Ok(before - self.rows.len())
+    }
+
+    /**
+     * Duplicates
+     *
+     * Row indices of every group of rows whose cells in the named columns
+     * repeat, in order of first appearance. No columns compares whole rows.
+     */
+    pub fn duplicates(&self, columns: &[&str]) -> Result<Vec<Vec<usize>>, CsvError> {
+        let positions = columns
+            .iter()
+            .map(|column| self.column_index(column))
+            .collect::<Result<Vec<usize>, CsvError>>()?;
+
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        let mut index: HashMap<Vec<&str>, usize> = HashMap::new();
+
+        for (row_index, row) in self.rows.iter().enumerate() {
+            let key = if positions.is_empty() {
+                row.iter().map(String::as_str).collect()
+            } else {
+                positions
+                    .iter()
+                    .map(|&position| row.get(position).map_or("", String::as_str))
+                    .collect::<Vec<&str>>()
+            };
+
+            match index.get(&key) {
+                Some(&group) => groups[group].push(row_index),
+                None => {
+                    index.insert(key, groups.len());
+                    groups.push(vec![row_index]);
+                }
+            }
+        }
+
+        groups.retain(|group| group.len() > 1);
+
+        Ok(groups)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> CsvFile {
+        let mut file = CsvFile::new();
+        file.push_head("k");
+        file.push_head("v");
+        for row in [["a", "1"], ["b", "2"], ["a", "3"], ["c", "4"], ["a", "1"]] {
+            file.push_row(&row).unwrap();
+        }
+        file
+    }
+
+    fn values(file: &CsvFile) -> Vec<&str> {
+        file.col("v").unwrap().collect()
+    }
+
+    #[test]
+    fn duplicates_groups_row_indices() {
+        let file = sample();
+
+        assert_eq!(file.duplicates(&["k"]).unwrap(), [vec![0, 2, 4]]);
+        assert_eq!(file.duplicates(&[]).unwrap(), [vec![0, 4]]);
+    }
+
+    #[test]
+    fn dedup_compares_whole_rows() {
+        let mut file = sample();
+
+        assert_eq!(file.dedup(), 1);
+        assert_eq!(values(&file), ["1", "2", "3", "4"]);
+    }
+
+    #[test]
+    fn dedup_by_keeps_the_chosen_row() {
+        for (keep, expected, removed) in [
+            (Keep::First, vec!["1", "2", "4"], 2),
+            (Keep::Last, vec!["2", "4", "1"], 2),
+            (Keep::None, vec!["2", "4"], 3),
+        ] {
+            let mut file = sample();
+
+            assert_eq!(file.dedup_by(&["k"], keep).unwrap(), removed, "{keep:?}");
+            assert_eq!(values(&file), expected, "{keep:?}");
+        }
+    }
+
+    #[test]
+    fn unknown_columns_fail() {
+        let mut file = sample();
+
+        assert!(matches!(
+            file.dedup_by(&["missing"], Keep::First),
+            Err(CsvError::UnknownColumn(_))
+        ));
+        assert_eq!(file, sample());
+    }
+}
@@ -0,0 +1,141 @@
+/**
+ * Escape
+ *
+ * How a quote is escaped inside a quoted field.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Escape {
+    /// Double the quote, e.g. `"say ""hi"""`.
+    #[default]
+    Double,
+    /// Prefix the next character with an escape char, e.g. `"say \"hi\""`.
+    Char(char),
+}
+
+/**
+ * Terminator
+ *
+ * Line terminator between records.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Terminator {
+    /// Read `\n`, `\r\n` or `\r`. Write `\n`.
+    #[default]
+    Any,
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+    /// `\r`
+    Cr,
+}
+
+impl Terminator {
+    /**
+     * As Str
+     *
+     * Terminator written after each record.
+     */
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Any | Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+            Self::Cr => "\r",
+        }
+    }
+}
+
+/**
+ * Dialect
+ *
+ * CSV format shared by readers and writers.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dialect {
+    /// Field delimiter. May be more than one character.
+    pub delimiter: String,
+    /// Quote char. `None` disables quoting.
+    pub quote: Option<char>,
+    /// Escape style inside quoted fields.
+    pub escape: Escape,
+    /// Line terminator.
+    pub terminator: Terminator,
+    /// Trim spaces and tabs around unquoted fields and outside quotes.
+    pub trim: bool,
+}
+
+impl Default for Dialect {
+    fn default() -> Self {
+        Self {
+            delimiter: ",".to_string(),
+            quote: Some('"'),
+            escape: Escape::Double,
+            terminator: Terminator::Any,
+            trim: false,
+        }
+    }
+}
+
+impl Dialect {
+    /**
+     * New
+     *
+     * Dialect with the given delimiter and RFC 4180 defaults otherwise.
+     */
+    pub fn new(delimiter: &str) -> Self {
+        Self {
+            delimiter: delimiter.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /**
+     * Tsv
+     *
+     * Tab separated values.
+     */
+    pub fn tsv() -> Self {
+        Self::new("\t")
+    }
+
+    /**
+     * Semicolon
+     *
+     * Semicolon separated values, common in European exports.
+     */
+    pub fn semicolon() -> Self {
+        Self::new(";")
+    }
+
+    /**
+     * Pipe
+     *
+     * Pipe separated values.
+     */
+    pub fn pipe() -> Self {
+        Self::new("|")
+    }
+
+    /**
+     * Quote Bytes
+     *
+     * UTF-8 bytes of the quote char, empty when quoting is disabled.
+     */
+    pub(crate) fn quote_bytes(&self) -> Vec<u8> {
+        self.quote
+            .map(|quote| quote.to_string().into_bytes())
+            .unwrap_or_default()
+    }
+
+    /**
+     * Escape Bytes
+     *
+     * UTF-8 bytes of the escape char, empty when quotes are doubled.
+     */
+    pub(crate) fn escape_bytes(&self) -> Vec<u8> {
+        match self.escape {
+            Escape::Double => Vec::new(),
+            Escape::Char(escape) => escape.to_string().into_bytes(),
+        }
+    }
+}
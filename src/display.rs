@@ -0,0 +1,754 @@
+use std::fmt;
+
+use crate::writer::is_numeric;
+use crate::CsvFile;
+
+const ELLIPSIS: char = '…';
+
+/**
+ * Border Style
+ *
+ * Lines drawn around and between table cells.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BorderStyle {
+    /// `+`, `-` and `|`.
+    #[default]
+    Ascii,
+    /// Box-drawing characters.
+    Unicode,
+    /// Columns separated by spaces only.
+    None,
+}
+
+/**
+ * Border
+ *
+ * Characters for one horizontal rule: left, fill, joint, right.
+ */
+type Border = [char; 4];
+
+impl BorderStyle {
+    fn top(&self) -> Option<Border> {
+        match self {
+            Self::Ascii => Some(['+', '-', '+', '+']),
+            Self::Unicode => Some(['┌', '─', '┬', '┐']),
+            Self::None => None,
+        }
+    }
+
+    fn middle(&self) -> Option<Border> {
+        match self {
+            Self::Ascii => Some(['+', '-', '+', '+']),
+            Self::Unicode => Some(['├', '─', '┼', '┤']),
+            Self::None => None,
+        }
+    }
+
+    fn bottom(&self) -> Option<Border> {
+        match self {
+            Self::Ascii => Some(['+', '-', '+', '+']),
+            Self::Unicode => Some(['└', '─', '┴', '┘']),
+            Self::None => None,
+        }
+    }
+
+    fn vertical(&self) -> Option<char> {
+        match self {
+            Self::Ascii => Some('|'),
+            Self::Unicode => Some('│'),
+            Self::None => None,
+        }
+    }
+}
+
+/**
+ * Table
+ *
+ * Aligned table rendering of a `CsvFile`. Column widths fit the widest cell,
+ * numeric columns are right-aligned and long cells are cut with an ellipsis.
+ */
+#[derive(Debug, Clone)]
+pub struct Table<'a> {
+    file: &'a CsvFile,
+    border: BorderStyle,
+    max_width: usize,
+    /// Rows shown from the start, or every row when `None`.
+    head: Option<usize>,
+    /// Rows shown from the end when rows are cut.
+    tail: usize,
+    /// Total width the table should fit in.
+    width: Option<usize>,
+    footer: bool,
+}
+
+impl<'a> Table<'a> {
+    /**
+     * Border
+     *
+     * Set the border style.
+     */
+    pub fn border(mut self, border: BorderStyle) -> Self {
+        self.border = border;
+        self
+    }
+
+    /**
+     * Max Width
+     *
+     * Set the widest a column may be, in terminal columns.
+     */
+    pub fn max_width(mut self, max_width: usize) -> Self {
+        self.max_width = max_width.max(1);
+        self
+    }
+
+    /**
+     * Head
+     *
+     * Show only the first `rows` rows, plus the `tail` rows.
+     */
+    pub fn head(mut self, rows: usize) -> Self {
+        self.head = Some(rows);
+        self
+    }
+
+    /**
+     * Tail
+     *
+     * Show the last `rows` rows when rows are cut by `head`.
+     */
+    pub fn tail(mut self, rows: usize) -> Self {
+        self.tail = rows;
+        self
+    }
+
+    /**
+     * All Rows
+     *
+     * Show every row.
+     */
+    pub fn all_rows(mut self) -> Self {
+        self.head = None;
+        self
+    }
+
+    /**
+     * Fit Width
+     *
+     * Hide trailing columns so the table is at most `width` terminal columns
+     * wide. At least one column is always shown.
+     */
+    pub fn fit_width(mut self, width: usize) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /**
+     * Footer
+     *
+     * Show a rows × columns summary below the table.
+     */
+    pub fn footer(mut self, footer: bool) -> Self {
+        self.footer = footer;
+        self
+    }
+
+    /**
+     * Table Width
+     *
+     * Total width of a table with the given column widths.
+     */
+    fn table_width(&self, widths: &[usize]) -> usize {
+        let cells = widths.iter().sum::<usize>();
+
+        match self.border {
+            BorderStyle::None => cells + 2 * widths.len().saturating_sub(1),
+            _ => cells + 3 * widths.len() + 1,
+        }
+    }
+
+    /**
+     * Rule
+     *
+     * Write a horizontal rule.
+     */
+    fn rule(&self, f: &mut fmt::Formatter<'_>, widths: &[usize], border: Border) -> fmt::Result {
+        let [left, fill, joint, right] = border;
+
+        write!(f, "{left}")?;
+        for (index, width) in widths.iter().enumerate() {
+            if index > 0 {
+                write!(f, "{joint}")?;
+            }
+            write!(f, "{}", fill.to_string().repeat(width + 2))?;
+        }
+        writeln!(f, "{right}")
+    }
+
+    /**
+     * Line
+     *
+     * Write one row of cells.
+     */
+    fn line(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        cells: &[String],
+        widths: &[usize],
+        numeric: &[bool],
+    ) -> fmt::Result {
+        let mut line = String::new();
+        let vertical = self.border.vertical();
+
+        for (index, width) in widths.iter().enumerate() {
+            let cell = cells.get(index).map_or("", String::as_str);
+            let pad = " ".repeat(width - display_width(cell));
+
+            match vertical {
+                Some(vertical) => line.push_str(&format!("{vertical} ")),
+                None if index > 0 => line.push_str("  "),
+                None => {}
+            }
+
+            if numeric[index] {
+                line.push_str(&pad);
+                line.push_str(cell);
+            } else {
+                line.push_str(cell);
+                line.push_str(&pad);
+            }
+
+            if vertical.is_some() {
+                line.push(' ');
+            }
+        }
+
+        match vertical {
+            Some(vertical) => writeln!(f, "{line}{vertical}"),
+            None => writeln!(f, "{}", line.trim_end()),
+        }
+    }
+
+    /**
+     * Inner Width
+     *
+     * Width inside the outer borders of a table with the given column widths.
+     */
+    fn inner_width(&self, widths: &[usize]) -> usize {
+        match self.border.vertical() {
+            Some(_) => self.table_width(widths) - 2,
+            None => self.table_width(widths),
+        }
+    }
+
+    /**
+     * Fit Marker
+     *
+     * Widen the last column so the marker line fits inside the table.
+     */
+    fn fit_marker(&self, widths: &mut [usize], marker: Option<&str>) {
+        let Some(marker) = marker else {
+            return;
+        };
+
+        let missing = display_width(marker).saturating_sub(self.inner_width(widths));
+        if let Some(last) = widths.last_mut() {
+            *last += missing;
+        }
+    }
+
+    /**
+     * Marker
+     *
+     * Write the line standing in for rows that aren't shown.
+     */
+    fn marker(&self, f: &mut fmt::Formatter<'_>, widths: &[usize], text: &str) -> fmt::Result {
+        let line = center(text, self.inner_width(widths));
+
+        match self.border.vertical() {
+            Some(vertical) => writeln!(f, "{vertical}{line}{vertical}"),
+            None => writeln!(f, "{}", line.trim_end()),
+        }
+    }
+}
+
+impl fmt::Display for Table<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = self.file;
+        let columns = file
+            .rows
+            .iter()
+            .map(Vec::len)
+            .chain([file.heads.len()])
+            .max()
+            .unwrap_or_default();
+
+        // Rows shown, and where the marker for the rest goes.
+        let total = file.rows.len();
+        let (shown, cut) = match self.head {
+            Some(head) if total > head + self.tail => {
+                let shown = file.rows[..head]
+                    .iter()
+                    .chain(file.rows[total - self.tail..].iter())
+                    .collect::<Vec<_>>();
+
+                (shown, Some(head))
+            }
+            _ => (file.rows.iter().collect(), None),
+        };
+        let marker = cut.map(|_| {
+            let hidden = total - shown.len();
+            format!(
+                "{ELLIPSIS} {} {ELLIPSIS}",
+                count(hidden, "more row", "more rows")
+            )
+        });
+
+        let fit = |cells: &[String]| -> Vec<String> {
+            (0..columns)
+                .map(|index| {
+                    truncate(
+                        &clean(cells.get(index).map_or("", String::as_str)),
+                        self.max_width,
+                    )
+                })
+                .collect()
+        };
+        let mut heads = fit(&file.heads);
+        let mut rows = shown.iter().map(|row| fit(row)).collect::<Vec<_>>();
+
+        let mut widths = (0..columns)
+            .map(|index| {
+                rows.iter()
+                    .chain([&heads])
+                    .map(|row| display_width(&row[index]))
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<usize>>();
+
+        // Columns whose non-empty cells are all numbers, with at least one.
+        let mut numeric = (0..columns)
+            .map(|index| {
+                let mut cells = shown
+                    .iter()
+                    .filter_map(|row| row.get(index))
+                    .filter(|cell| !cell.is_empty())
+                    .peekable();
+
+                cells.peek().is_some() && cells.all(|cell| is_numeric(cell.trim()))
+            })
+            .collect::<Vec<bool>>();
+
+        // Hide trailing columns behind an ellipsis column until the table fits.
+        let mut visible = columns;
+        if let Some(width) = self.width {
+            let fits = |visible: usize| {
+                let mut widths = widths[..visible].to_vec();
+                if visible < columns {
+                    widths.push(1);
+                }
+                self.fit_marker(&mut widths, marker.as_deref());
+                self.table_width(&widths) <= width
+            };
+
+            while visible > 1 && !fits(visible) {
+                visible -= 1;
+            }
+        }
+
+        if visible < columns {
+            for row in rows.iter_mut().chain([&mut heads]) {
+                row.truncate(visible);
+                row.push(ELLIPSIS.to_string());
+            }
+            widths.truncate(visible);
+            widths.push(1);
+            numeric.truncate(visible);
+            numeric.push(false);
+        }
+        self.fit_marker(&mut widths, marker.as_deref());
+
+        if columns > 0 {
+            if let Some(border) = self.border.top() {
+                self.rule(f, &widths, border)?;
+            }
+
+            if !file.heads.is_empty() {
+                self.line(f, &heads, &widths, &numeric)?;
+
+                if let Some(border) = self.border.middle() {
+                    self.rule(f, &widths, border)?;
+                }
+            }
+
+            for (index, row) in rows.iter().enumerate() {
+                if let (Some(at), Some(marker)) = (cut, &marker) {
+                    if index == at {
+                        self.marker(f, &widths, marker)?;
+                    }
+                }
+
+                self.line(f, row, &widths, &numeric)?;
+            }
+
+            if let (Some(at), Some(marker)) = (cut, &marker) {
+                if at == rows.len() {
+                    self.marker(f, &widths, marker)?;
+                }
+            }
+
+            if let Some(border) = self.border.bottom() {
+                self.rule(f, &widths, border)?;
+            }
+        }
+
+        if self.footer {
+            write!(
+                f,
+                "{} × {}",
+                count(total, "row", "rows"),
+                count(columns, "column", "columns")
+            )?;
+
+            if visible < columns {
+                write!(f, " ({} hidden)", group_digits(columns - visible))?;
+            }
+
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CsvFile {
+    /**
+     * Table
+     *
+     * Table renderer with ASCII borders and columns up to 40 wide.
+     */
+    pub fn table(&self) -> Table<'_> {
+        Table {
+            file: self,
+            border: BorderStyle::default(),
+            max_width: 40,
+            head: None,
+            tail: 0,
+            width: None,
+            footer: false,
+        }
+    }
+
+    /**
+     * Preview
+     *
+     * Table renderer for large files: the first and last 5 rows, with a
+     * marker for the rest and a rows × columns footer.
+     */
+    pub fn preview(&self) -> Table<'_> {
+        self.table().head(5).tail(5).footer(true)
+    }
+}
+
+impl fmt::Display for CsvFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.table().fmt(f)
+    }
+}
+
+/**
+ * Count
+ *
+ * Number with digit grouping and a singular or plural noun.
+ */
+pub(crate) fn count(n: usize, one: &str, many: &str) -> String {
+    let noun = if n == 1 { one } else { many };
+
+    format!("{} {noun}", group_digits(n))
+}
+
+/**
+ * Group Digits
+ *
+ * Number with `,` between groups of three digits, e.g. `1,204,331`.
+ */
+fn group_digits(n: usize) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::new();
+
+    for (index, digit) in digits.chars().enumerate() {
+        if index > 0 && (digits.len() - index) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(digit);
+    }
+
+    grouped
+}
+
+/**
+ * Center
+ *
+ * Pad text on both sides to `width` terminal columns.
+ */
+fn center(text: &str, width: usize) -> String {
+    let pad = width.saturating_sub(display_width(text));
+    let left = pad / 2;
+
+    format!("{}{text}{}", " ".repeat(left), " ".repeat(pad - left))
+}
+
+/**
+ * Clean
+ *
+ * Make a cell fit on one line: line breaks become `↵`, other control
+ * characters become spaces.
+ */
+fn clean(cell: &str) -> String {
+    cell.replace("\r\n", "\n")
+        .chars()
+        .map(|c| match c {
+            '\n' | '\r' => '↵',
+            c if c.is_control() => ' ',
+            c => c,
+        })
+        .collect()
+}
+
+/**
+ * Truncate
+ *
+ * Cut a cell to `max` terminal columns, ending with an ellipsis when cut.
+ */
+fn truncate(cell: &str, max: usize) -> String {
+    if display_width(cell) <= max {
+        return cell.to_string();
+    }
+
+    let mut cut = String::new();
+    let mut width = 0;
+
+    for c in cell.chars() {
+        let char_width = char_width(c);
+        if width + char_width > max - 1 {
+            break;
+        }
+
+        cut.push(c);
+        width += char_width;
+    }
+
+    cut.push(ELLIPSIS);
+    cut
+}
+
+/**
+ * Display Width
+ *
+ * Terminal columns taken by a string.
+ */
+pub(crate) fn display_width(text: &str) -> usize {
+    text.chars().map(char_width).sum()
+}
+
+/**
+ * Char Width
+ *
+ * Terminal columns taken by a char: 0 for combining and zero-width chars,
+ * 2 for wide CJK and emoji, 1 otherwise.
+ */
+fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F
+        | 0x0483..=0x0489
+        | 0x0591..=0x05BD
+        | 0x0610..=0x061A
+        | 0x064B..=0x065F
+        | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF
+        | 0x200B..=0x200F
+        | 0x20D0..=0x20FF
+        | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F
+        | 0xE0100..=0xE01EF => 0,
+        0x1100..=0x115F
+        | 0x231A..=0x231B
+        | 0x2329..=0x232A
+        | 0x23E9..=0x23EC
+        | 0x23F0
+        | 0x23F3
+        | 0x25FD..=0x25FE
+        | 0x2614..=0x2615
+        | 0x2648..=0x2653
+        | 0x267F
+        | 0x2693
+        | 0x26A1
+        | 0x26AA..=0x26AB
+        | 0x26BD..=0x26BE
+        | 0x26C4..=0x26C5
+        | 0x26CE
+        | 0x26D4
+        | 0x26EA
+        | 0x26F2..=0x26F3
+        | 0x26F5
+        | 0x26FA
+        | 0x26FD
+        | 0x2705
+        | 0x270A..=0x270B
+        | 0x2728
+        | 0x274C
+        | 0x274E
+        | 0x2753..=0x2755
+        | 0x2757
+        | 0x2795..=0x2797
+        | 0x27B0
+        | 0x27BF
+        | 0x2B1B..=0x2B1C
+        | 0x2B50
+        | 0x2B55
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xA960..=0xA97F
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE10..=0xFE19
+        | 0xFE30..=0xFE6F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F004
+        | 0x1F0CF
+        | 0x1F18E
+        | 0x1F191..=0x1F19A
+        | 0x1F200..=0x1F251
+        | 0x1F300..=0x1F64F
+        | 0x1F680..=0x1F6FF
+        | 0x1F7E0..=0x1F7EB
+        | 0x1F90C..=0x1F9FF
+        | 0x1FA70..=0x1FAFF
+        | 0x20000..=0x3FFFD => 2,
+        _ if c.is_control() => 0,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(columns: &[&str], rows: usize) -> CsvFile {
+        let mut file = CsvFile::new();
+        for head in columns {
+            file.push_head(head);
+        }
+        for index in 0..rows {
+            let cell = index.to_string();
+            file.push_row(&vec![cell.as_str(); columns.len()]).unwrap();
+        }
+        file
+    }
+
+    fn widths(table: &str) -> Vec<usize> {
+        table.lines().map(display_width).collect()
+    }
+
+    #[test]
+    fn marker_fits_inside_narrow_tables() {
+        let file = numbers(&["N"], 20);
+        let table = file.preview().footer(false).to_string();
+
+        assert!(table.contains("|… 10 more rows …|"));
+        assert!(widths(&table).iter().all(|width| *width == 18));
+    }
+
+    #[test]
+    fn fit_width_counts_the_marker() {
+        let file = numbers(&["a", "b", "c", "d"], 20);
+        let table = file.preview().head(1).tail(1).fit_width(18).to_string();
+        let lines = widths(&table);
+
+        // Every bordered line, marker included, is exactly as wide as allowed.
+        assert!(lines[..lines.len() - 1].iter().all(|width| *width == 18));
+        assert!(table.ends_with("20 rows × 4 columns (2 hidden)\n"));
+    }
+
+    #[test]
+    fn groups_digits() {
+        assert_eq!(group_digits(0), "0");
+        assert_eq!(group_digits(999), "999");
+        assert_eq!(group_digits(1_204_331), "1,204,331");
+    }
+
+    fn sample() -> CsvFile {
+        let mut file = CsvFile::new();
+        file.push_head("name");
+        file.push_head("n");
+        file.push_row(&["日本語", "7"]).unwrap();
+        file.push_row(&["😀x", "1250"]).unwrap();
+        file.push_row(&["a very long cell", "-3.5"]).unwrap();
+        file
+    }
+
+    #[test]
+    fn each_border_style() {
+        let file = sample();
+        let table = |border| file.table().border(border).max_width(8).to_string();
+
+        assert_eq!(
+            table(BorderStyle::Ascii),
+            "\
++----------+------+
+| name     |    n |
++----------+------+
+| 日本語   |    7 |
+| 😀x      | 1250 |
+| a very … | -3.5 |
++----------+------+
+"
+        );
+        assert_eq!(
+            table(BorderStyle::Unicode),
+            "\
+┌──────────┬──────┐
+│ name     │    n │
+├──────────┼──────┤
+│ 日本語   │    7 │
+│ 😀x      │ 1250 │
+│ a very … │ -3.5 │
+└──────────┴──────┘
+"
+        );
+        assert_eq!(
+            table(BorderStyle::None),
+            "\
+name         n
+日本語       7
+😀x       1250
+a very …  -3.5
+"
+        );
+    }
+
+    #[test]
+    fn wide_characters_count_double() {
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("😀x"), 3);
+        assert_eq!(display_width("é"), 1);
+
+        let table = sample().table().to_string();
+        assert!(widths(&table).windows(2).all(|pair| pair[0] == pair[1]));
+    }
+
+    #[test]
+    fn max_width_cuts_with_an_ellipsis() {
+        let table = sample().table().max_width(5).to_string();
+
+        assert!(table.contains("| a ve… |"), "{table}");
+        assert!(table.contains("| 日本… |"), "{table}");
+        assert!(widths(&table).windows(2).all(|pair| pair[0] == pair[1]));
+    }
+}
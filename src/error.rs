@@ -0,0 +1,151 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/**
+ * Position
+ *
+ * Where in the input an error happened.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Position {
+    /// Byte offset from the start of the input.
+    pub byte: u64,
+    /// Line number, starting at 1.
+    pub line: u64,
+    /// Field index within the record, starting at 0.
+    pub field: usize,
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, field {} (byte {})",
+            self.line, self.field, self.byte
+        )
+    }
+}
+
+/**
+ * CSV Error
+ *
+ * Errors from reading, writing and manipulating CSV data.
+ */
+#[derive(Debug)]
+pub enum CsvError {
+    /// Underlying I/O failure.
+    Io(io::Error),
+    /// A quoted field was never closed.
+    UnterminatedQuote { position: Position },
+    /// A field isn't valid UTF-8.
+    InvalidUtf8 { position: Position },
+    /// A record has a different number of fields than the header.
+    RaggedRow {
+        position: Position,
+        expected: usize,
+        found: usize,
+    },
+    /// The input has no records.
+    Empty,
+    /// A head, row or cell index is past the end.
+    OutOfBounds { index: usize, len: usize },
+    /// A row doesn't have as many cells as the heads.
+    ShapeMismatch { expected: usize, found: usize },
+    /// No head has the given name.
+    UnknownColumn(String),
+    /// A head with the given name already exists.
+    DuplicateColumn(String),
+    /// Two files don't have the same heads.
+    HeadMismatch {
+        expected: Vec<String>,
+        found: Vec<String>,
+    },
+    /// A cell that must be a number isn't one.
+    InvalidNumber { column: String, value: String },
+    /// A row couldn't be deserialized into the target type.
+    Deserialize {
+        /// Row index, not counting the heads.
+        row: usize,
+        /// Head of the cell, when known.
+        field: Option<String>,
+        message: String,
+    },
+    /// A record couldn't be serialized into a row.
+    Serialize { row: usize, message: String },
+    /// JSON input couldn't be read.
+    InvalidJson {
+        line: u64,
+        column: u64,
+        message: String,
+    },
+    /// Markdown input couldn't be read as a table.
+    InvalidMarkdown { message: String },
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "I/O error: {err}"),
+            Self::UnterminatedQuote { position } => {
+                write!(f, "unterminated quoted field at {position}")
+            }
+            Self::InvalidUtf8 { position } => write!(f, "invalid UTF-8 at {position}"),
+            Self::RaggedRow {
+                position,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected {expected} fields but found {found} at {position}"
+            ),
+            Self::Empty => write!(f, "empty input"),
+            Self::OutOfBounds { index, len } => {
+                write!(f, "index {index} out of bounds for length {len}")
+            }
+            Self::ShapeMismatch { expected, found } => {
+                write!(f, "expected {expected} cells but found {found}")
+            }
+            Self::UnknownColumn(name) => write!(f, "unknown column {name:?}"),
+            Self::DuplicateColumn(name) => write!(f, "duplicate column {name:?}"),
+            Self::HeadMismatch { expected, found } => {
+                write!(f, "expected heads {expected:?} but found {found:?}")
+            }
+            Self::InvalidNumber { column, value } => {
+                write!(f, "invalid number {value:?} in column {column:?}")
+            }
+            Self::Deserialize {
+                row,
+                field: Some(field),
+                message,
+            } => write!(f, "row {row}, field {field:?}: {message}"),
+            Self::Deserialize {
+                row,
+                field: None,
+                message,
+            } => write!(f, "row {row}: {message}"),
+            Self::Serialize { row, message } => write!(f, "record {row}: {message}"),
+            Self::InvalidJson {
+                line,
+                column,
+                message,
+            } => write!(f, "invalid JSON at line {line}, column {column}: {message}"),
+            Self::InvalidMarkdown { message } => write!(f, "invalid Markdown: {message}"),
+        }
+    }
+}
+
+impl Error for CsvError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for CsvError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
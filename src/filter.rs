@@ -0,0 +1,148 @@
+use crate::{CsvFile, RowRef};
+
+impl CsvFile {
+    /**
+     * Retain
+     *
+     * Keep only the rows for which `f` returns `true`.
+     */
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(RowRef<'_>) -> bool,
+    {
+        let heads = &self.heads;
+        let mut index = 0;
+
+        self.rows.retain(|row| {
+            let keep = f(RowRef::new(heads, row, index));
+            index += 1;
+            keep
+        });
+    }
+
+    /**
+     * Filter
+     *
+     * New file with the same heads and only the rows for which `f` returns
+     * `true`.
+     */
+    pub fn filter<F>(&self, mut f: F) -> CsvFile
+    where
+        F: FnMut(RowRef<'_>) -> bool,
+    {
+        let mut file = self.empty_like();
+
+        file.rows = self
+            .iter()
+            .filter(|row| f(*row))
+            .map(|row| row.cells().to_vec())
+            .collect();
+
+        file
+    }
+
+    /**
+     * Drain Where
+     *
+     * Remove the rows for which `f` returns `true` and return them as a new
+     * file with the same heads.
+     */
+    pub fn drain_where<F>(&mut self, mut f: F) -> CsvFile
+    where
+        F: FnMut(RowRef<'_>) -> bool,
+    {
+        let mut drained = self.empty_like();
+        let rows = std::mem::take(&mut self.rows);
+
+        for (index, row) in rows.into_iter().enumerate() {
+            if f(RowRef::new(&self.heads, &row, index)) {
+                drained.rows.push(row);
+            } else {
+                self.rows.push(row);
+            }
+        }
+
+        drained
+    }
+
+    /**
+     * Empty Like
+     *
+     * New file with the same heads and shape policy but no rows.
+     */
+    pub(crate) fn empty_like(&self) -> CsvFile {
+        CsvFile {
+            heads: self.heads.clone(),
+            rows: Vec::new(),
+            shape: self.shape.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers() -> CsvFile {
+        let mut file = CsvFile::new();
+        file.push_head("n");
+        for n in ["1", "2", "3", "4", "5"] {
+            file.push_row(&[n]).unwrap();
+        }
+        file
+    }
+
+    fn column(file: &CsvFile) -> Vec<&str> {
+        file.col("n").unwrap().collect()
+    }
+
+    fn is_even(row: RowRef<'_>) -> bool {
+        row.get_parsed::<u32>("n").unwrap().unwrap() % 2 == 0
+    }
+
+    #[test]
+    fn retain_sees_original_indices() {
+        let mut file = numbers();
+        let mut seen = Vec::new();
+
+        file.retain(|row| {
+            seen.push(row.index());
+            is_even(row)
+        });
+
+        assert_eq!(seen, [0, 1, 2, 3, 4]);
+        assert_eq!(column(&file), ["2", "4"]);
+    }
+
+    #[test]
+    fn filter_leaves_the_file_alone() {
+        let file = numbers();
+        let mut seen = Vec::new();
+
+        let even = file.filter(|row| {
+            seen.push(row.index());
+            is_even(row)
+        });
+
+        assert_eq!(seen, [0, 1, 2, 3, 4]);
+        assert_eq!(even.heads(), &["n"]);
+        assert_eq!(column(&even), ["2", "4"]);
+        assert_eq!(file, numbers());
+    }
+
+    #[test]
+    fn drain_where_splits_the_rows() {
+        let mut file = numbers();
+        let mut seen = Vec::new();
+
+        let drained = file.drain_where(|row| {
+            seen.push(row.index());
+            row.index() >= 3
+        });
+
+        assert_eq!(seen, [0, 1, 2, 3, 4]);
+        assert_eq!(column(&drained), ["4", "5"]);
+        assert_eq!(drained.heads(), &["n"]);
+        assert_eq!(column(&file), ["1", "2", "3"]);
+    }
+}
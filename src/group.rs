@@ -0,0 +1,480 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{CsvError, CsvFile, RowRef};
+
+/**
+ * Agg
+ *
+ * Aggregation over the rows of each group.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Agg {
+    /// Number of rows.
+    Count,
+    /// Sum of the numbers in a column. Empty cells are skipped.
+    Sum(String),
+    /// Mean of the numbers in a column. Empty cells are skipped.
+    Mean(String),
+    /// Smallest cell, compared as numbers when every non-empty cell is one.
+    Min(String),
+    /// Largest cell, compared as numbers when every non-empty cell is one.
+    Max(String),
+    /// Cell of the first row.
+    First(String),
+    /// Cell of the last row.
+    Last(String),
+    /// Number of distinct non-empty cells.
+    CountDistinct(String),
+    /// Cells joined with a separator.
+    Concat(String, String),
+}
+
+impl Agg {
+    /**
+     * Name
+     *
+     * Head of the aggregated column, e.g. `count` or `sum(Amount)`.
+     */
+    pub fn name(&self) -> String {
+        match self {
+            Self::Count => "count".to_string(),
+            Self::Sum(column) => format!("sum({column})"),
+            Self::Mean(column) => format!("mean({column})"),
+            Self::Min(column) => format!("min({column})"),
+            Self::Max(column) => format!("max({column})"),
+            Self::First(column) => format!("first({column})"),
+            Self::Last(column) => format!("last({column})"),
+            Self::CountDistinct(column) => format!("count_distinct({column})"),
+            Self::Concat(column, _) => format!("concat({column})"),
+        }
+    }
+
+    fn column(&self) -> Option<&str> {
+        match self {
+            Self::Count => None,
+            Self::Sum(column)
+            | Self::Mean(column)
+            | Self::Min(column)
+            | Self::Max(column)
+            | Self::First(column)
+            | Self::Last(column)
+            | Self::CountDistinct(column)
+            | Self::Concat(column, _) => Some(column),
+        }
+    }
+
+    /**
+     * Apply
+     *
+     * Aggregate the cells of one group.
+     */
+    fn apply(&self, cells: &[&str]) -> Result<String, CsvError> {
+        let column = self.column().unwrap_or_default();
+        let values = cells.iter().copied().filter(|cell| !cell.trim().is_empty());
+
+        let cell = match self {
+            Self::Count => cells.len().to_string(),
+            Self::Sum(_) => sum(column, values)?.0,
+            Self::Mean(_) => match sum(column, values)? {
+                (_, 0) => String::new(),
+                (total, count) => {
+                    let total = total.parse::<f64>().unwrap_or_default();
+
+                    (total / count as f64).to_string()
+                }
+            },
+            Self::Min(_) => extreme(values, true),
+            Self::Max(_) => extreme(values, false),
+            Self::First(_) => cells.first().copied().unwrap_or_default().to_string(),
+            Self::Last(_) => cells.last().copied().unwrap_or_default().to_string(),
+            Self::CountDistinct(_) => values.collect::<HashSet<&str>>().len().to_string(),
+            Self::Concat(_, separator) => cells.join(separator),
+        };
+
+        Ok(cell)
+    }
+}
+
+/**
+ * Sum
+ *
+ * Total of the cells and how many there are. Integers are summed exactly;
+ * other numbers are rounded to the most decimal places among the cells, so
+ * `0.1 + 0.2` is `0.3`.
+ */
+fn sum<'a>(
+    column: &str,
+    values: impl Iterator<Item = &'a str>,
+) -> Result<(String, usize), CsvError> {
+    let values = values.map(str::trim).collect::<Vec<&str>>();
+
+    let integers = values
+        .iter()
+        .map(|value| value.parse::<i128>())
+        .collect::<Result<Vec<i128>, _>>();
+    if let Ok(integers) = integers {
+        let total = integers
+            .iter()
+            .try_fold(0i128, |total, number| total.checked_add(*number));
+
+        if let Some(total) = total {
+            return Ok((total.to_string(), values.len()));
+        }
+    }
+
+    let total = numbers(column, values.iter().copied())?
+        .iter()
+        .fold(0.0, |total, number| total + number);
+
+    let places = values
+        .iter()
+        .map(|value| decimal_places(value))
+        .collect::<Option<Vec<usize>>>()
+        .and_then(|places| places.into_iter().max());
+
+    let total = match places {
+        Some(places) => format!("{total:.places$}"),
+        None => total.to_string(),
+    };
+
+    Ok((total, values.len()))
+}
+
+/**
+ * Decimal Places
+ *
+ * Digits after the decimal point of a plain decimal, or `None` for numbers
+ * like `1e-3` or `inf`.
+ */
+fn decimal_places(value: &str) -> Option<usize> {
+    if !value
+        .bytes()
+        .all(|byte| byte.is_ascii_digit() || matches!(byte, b'.' | b'+' | b'-'))
+    {
+        return None;
+    }
+
+    Some(
+        value
+            .split_once('.')
+            .map_or(0, |(_, fraction)| fraction.len()),
+    )
+}
+
+fn numbers<'a>(column: &str, values: impl Iterator<Item = &'a str>) -> Result<Vec<f64>, CsvError> {
+    values
+        .map(|value| {
+            value.trim().parse().map_err(|_| CsvError::InvalidNumber {
+                column: column.to_string(),
+                value: value.to_string(),
+            })
+        })
+        .collect()
+}
+
+/**
+ * Extreme
+ *
+ * Smallest or largest value, as numbers when they all parse.
+ */
+fn extreme<'a>(values: impl Iterator<Item = &'a str> + Clone, min: bool) -> String {
+    let parsed = values
+        .clone()
+        .map(|value| value.trim().parse::<f64>().map(|number| (number, value)))
+        .collect::<Result<Vec<(f64, &str)>, _>>();
+
+    let found = match parsed {
+        Ok(numbers) if min => numbers.into_iter().min_by(|a, b| a.0.total_cmp(&b.0)),
+        Ok(numbers) => numbers.into_iter().max_by(|a, b| a.0.total_cmp(&b.0)),
+        Err(_) if min => values.min().map(|value| (0.0, value)),
+        Err(_) => values.max().map(|value| (0.0, value)),
+    };
+
+    found
+        .map(|(_, value)| value.to_string())
+        .unwrap_or_default()
+}
+
+/**
+ * Group
+ *
+ * Rows sharing the same key cells.
+ */
+#[derive(Debug, Clone)]
+pub struct Group<'a> {
+    key: Vec<&'a str>,
+    rows: Vec<RowRef<'a>>,
+}
+
+impl<'a> Group<'a> {
+    /**
+     * Key
+     *
+     * Key cells, in the order of the group columns.
+     */
+    pub fn key(&self) -> &[&'a str] {
+        &self.key
+    }
+
+    /**
+     * Rows
+     *
+     * Rows of the group, in file order.
+     */
+    pub fn rows(&self) -> &[RowRef<'a>] {
+        &self.rows
+    }
+}
+
+/**
+ * Group By
+ *
+ * Rows of a file grouped by key columns. Groups keep the order in which
+ * their key first appears.
+ */
+#[derive(Debug, Clone)]
+pub struct GroupBy<'a> {
+    file: &'a CsvFile,
+    columns: Vec<String>,
+    groups: Vec<Group<'a>>,
+}
+
+impl<'a> GroupBy<'a> {
+    /**
+     * Len
+     *
+     * Number of groups.
+     */
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /**
+     * Is Empty
+     *
+     * Whether there are no groups.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /**
+     * Groups
+     *
+     * Every group, in order of first appearance.
+     */
+    pub fn groups(&self) -> &[Group<'a>] {
+        &self.groups
+    }
+
+    /**
+     * Aggregate
+     *
+     * New file with one row per group: the key cells followed by one cell per
+     * aggregation, headed by `Agg::name`.
+     */
+    pub fn aggregate(&self, aggs: &[Agg]) -> Result<CsvFile, CsvError> {
+        let positions = aggs
+            .iter()
+            .map(|agg| {
+                agg.column()
+                    .map(|column| self.file.column_index(column))
+                    .transpose()
+            })
+            .collect::<Result<Vec<Option<usize>>, CsvError>>()?;
+
+        let mut file = CsvFile::new();
+        file.heads = self.columns.clone();
+        file.heads.extend(aggs.iter().map(Agg::name));
+
+        for group in self.groups.iter() {
+            let mut row = group
+                .key
+                .iter()
+                .map(|cell| cell.to_string())
+                .collect::<Vec<String>>();
+
+            for (agg, position) in aggs.iter().zip(positions.iter()) {
+                let cells = group
+                    .rows
+                    .iter()
+                    .map(|row| position.and_then(|i| row.get_at(i)).unwrap_or_default())
+                    .collect::<Vec<&str>>();
+
+                row.push(agg.apply(&cells)?);
+            }
+
+            file.rows.push(row);
+        }
+
+        Ok(file)
+    }
+}
+
+impl CsvFile {
+    /**
+     * Group By
+     *
+     * Group the rows by the cells of the named columns.
+     */
+    pub fn group_by(&self, columns: &[&str]) -> Result<GroupBy<'_>, CsvError> {
+        let positions = columns
+            .iter()
+            .map(|column| self.column_index(column))
+            .collect::<Result<Vec<usize>, CsvError>>()?;
+
+        let mut groups: Vec<Group<'_>> = Vec::new();
+        let mut index: HashMap<Vec<&str>, usize> = HashMap::new();
+
+        for row in self.iter() {
+            let key = positions
+                .iter()
+                .map(|&position| row.get_at(position).unwrap_or_default())
+                .collect::<Vec<&str>>();
+
+            match index.get(&key) {
+                Some(&group) => groups[group].rows.push(row),
+                None => {
+                    index.insert(key.clone(), groups.len());
+                    groups.push(Group {
+                        key,
+                        rows: vec![row],
+                    });
+                }
+            }
+        }
+
+        Ok(GroupBy {
+            file: self,
+            columns: columns.iter().map(|column| column.to_string()).collect(),
+            groups,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(heads: &[&str], rows: &[&[&str]]) -> CsvFile {
+        let mut file = CsvFile::new();
+        for head in heads {
+            file.push_head(head);
+        }
+        for row in rows {
+            file.push_row(row).unwrap();
+        }
+        file
+    }
+
+    fn sales() -> CsvFile {
+        file(
+            &["region", "amount", "rep"],
+            &[
+                &["north", "10", "ann"],
+                &["south", "4", "bob"],
+                &["north", "", "cy"],
+                &["north", "5", "ann"],
+                &["south", "9", "bob"],
+            ],
+        )
+    }
+
+    fn aggregate(agg: Agg) -> Vec<Vec<String>> {
+        sales()
+            .group_by(&["region"])
+            .unwrap()
+            .aggregate(&[agg])
+            .unwrap()
+            .rows()
+            .clone()
+    }
+
+    fn sum(cells: &[&str]) -> String {
+        Agg::Sum("n".into()).apply(cells).unwrap()
+    }
+
+    #[test]
+    fn integer_sums_are_exact() {
+        assert_eq!(sum(&["9007199254740993", "1"]), "9007199254740994");
+        assert_eq!(sum(&["-3", " 5 ", ""]), "2");
+        assert_eq!(sum(&[]), "0");
+    }
+
+    #[test]
+    fn decimal_sums_keep_the_input_precision() {
+        assert_eq!(sum(&["0.1", "0.2"]), "0.3");
+        assert_eq!(sum(&["1.25", "2", "0.5"]), "3.75");
+        assert_eq!(sum(&["1e2", "0.5"]), "100.5");
+
+        let mean = Agg::Mean("n".into()).apply(&["0.1", "0.2"]).unwrap();
+        assert_eq!(mean, "0.15");
+    }
+
+    #[test]
+    fn groups_keep_first_appearance_order() {
+        let file = sales();
+        let groups = file.group_by(&["region"]).unwrap();
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups.groups()[0].key(), &["north"]);
+        assert_eq!(groups.groups()[0].rows().len(), 3);
+        assert_eq!(groups.groups()[1].key(), &["south"]);
+    }
+
+    #[test]
+    fn aggregate_heads_are_keys_then_names() {
+        let file = sales()
+            .group_by(&["region"])
+            .unwrap()
+            .aggregate(&[Agg::Count, Agg::Sum("amount".into())])
+            .unwrap();
+
+        assert_eq!(file.heads(), &["region", "count", "sum(amount)"]);
+    }
+
+    #[test]
+    fn each_aggregation() {
+        let cells = |agg: Agg| {
+            aggregate(agg)
+                .into_iter()
+                .map(|row| row[1].clone())
+                .collect::<Vec<String>>()
+        };
+
+        assert_eq!(cells(Agg::Count), ["3", "2"]);
+        assert_eq!(cells(Agg::Sum("amount".into())), ["15", "13"]);
+        assert_eq!(cells(Agg::Mean("amount".into())), ["7.5", "6.5"]);
+        assert_eq!(cells(Agg::Min("amount".into())), ["5", "4"]);
+        assert_eq!(cells(Agg::Max("amount".into())), ["10", "9"]);
+        assert_eq!(cells(Agg::Min("rep".into())), ["ann", "bob"]);
+        assert_eq!(cells(Agg::Max("rep".into())), ["cy", "bob"]);
+        assert_eq!(cells(Agg::First("amount".into())), ["10", "4"]);
+        assert_eq!(cells(Agg::Last("rep".into())), ["ann", "bob"]);
+        assert_eq!(cells(Agg::CountDistinct("rep".into())), ["2", "1"]);
+        assert_eq!(
+            cells(Agg::Concat("rep".into(), "/".into())),
+            ["ann/cy/ann", "bob/bob"]
+        );
+    }
+
+    #[test]
+    fn mean_of_empty_cells_is_empty() {
+        assert_eq!(Agg::Mean("n".into()).apply(&["", " "]).unwrap(), "");
+    }
+
+    #[test]
+    fn non_numbers_fail_numeric_aggregations() {
+        let file = file(&["k", "n"], &[&["a", "1"], &["a", "x"]]);
+        let groups = file.group_by(&["k"]).unwrap();
+
+        assert!(matches!(
+            groups.aggregate(&[Agg::Sum("n".into())]),
+            Err(CsvError::InvalidNumber { column, value }) if column == "n" && value == "x"
+        ));
+        assert!(matches!(
+            groups.aggregate(&[Agg::Mean("missing".into())]),
+            Err(CsvError::UnknownColumn(_))
+        ));
+    }
+}
@@ -0,0 +1,204 @@
+use crate::display::count;
+use crate::CsvFile;
+
+/**
+ * HTML Options
+ *
+ * Options for writing HTML tables.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HtmlOptions {
+    /// `class` attribute of the `<table>`.
+    pub class: Option<String>,
+    /// Text of the `<caption>`.
+    pub caption: Option<String>,
+    /// Mark body rows with alternating `odd` and `even` classes.
+    pub striped: bool,
+    /// Most body rows written. The rest are counted in a `<tfoot>` row.
+    pub max_rows: Option<usize>,
+}
+
+impl CsvFile {
+    /**
+     * To HTML
+     *
+     * HTML `<table>` with the heads in `<thead>` and every cell escaped.
+     */
+    pub fn to_html(&self) -> String {
+        self.to_html_with(&HtmlOptions::default())
+    }
+
+    /**
+     * To HTML With
+     *
+     * HTML `<table>` with the given options. Line breaks in cells are
+     * written as `<br>`.
+     */
+    pub fn to_html_with(&self, options: &HtmlOptions) -> String {
+        let columns = self
+            .rows
+            .iter()
+            .map(Vec::len)
+            .chain([self.heads.len()])
+            .max()
+            .unwrap_or_default();
+        let shown = options
+            .max_rows
+            .map_or(self.rows.len(), |max| max.min(self.rows.len()));
+
+        let mut html = String::new();
+
+        match &options.class {
+            Some(class) => html.push_str(&format!("<table class=\"{}\">\n", escape(class))),
+            None => html.push_str("<table>\n"),
+        }
+
+        if let Some(caption) = &options.caption {
+            html.push_str(&format!("  <caption>{}</caption>\n", escape(caption)));
+        }
+
+        if !self.heads.is_empty() {
+            html.push_str("  <thead>\n    <tr>");
+            push_cells(&mut html, "th", &self.heads, columns);
+            html.push_str("</tr>\n  </thead>\n");
+        }
+
+        html.push_str("  <tbody>\n");
+        for (index, row) in self.rows[..shown].iter().enumerate() {
+            match (options.striped, index % 2) {
+                (true, 0) => html.push_str("    <tr class=\"odd\">"),
+                (true, _) => html.push_str("    <tr class=\"even\">"),
+                (false, _) => html.push_str("    <tr>"),
+            }
+            push_cells(&mut html, "td", row, columns);
+            html.push_str("</tr>\n");
+        }
+        html.push_str("  </tbody>\n");
+
+        if shown < self.rows.len() {
+            let more = count(self.rows.len() - shown, "more row", "more rows");
+            html.push_str(&format!(
+                "  <tfoot>\n    <tr><td colspan=\"{}\">{more}</td></tr>\n  </tfoot>\n",
+                columns.max(1)
+            ));
+        }
+
+        html.push_str("</table>\n");
+        html
+    }
+}
+
+/**
+ * Push Cells
+ *
+ * Push `columns` escaped cells in `tag` elements. Missing cells are empty.
+ */
+fn push_cells(html: &mut String, tag: &str, cells: &[String], columns: usize) {
+    for index in 0..columns {
+        let cell = cells.get(index).map_or("", String::as_str);
+        let cell = escape(&cell.replace("\r\n", "\n")).replace(['\n', '\r'], "<br>");
+
+        html.push_str(&format!("<{tag}>{cell}</{tag}>"));
+    }
+}
+
+/**
+ * Escape
+ *
+ * Text with HTML special characters replaced by entities, safe in both
+ * element content and quoted attributes.
+ */
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(heads: &[&str], rows: &[&[&str]]) -> CsvFile {
+        let mut file = CsvFile::new();
+        for head in heads {
+            file.push_head(head);
+        }
+        for row in rows {
+            file.push_row(row).unwrap();
+        }
+        file
+    }
+
+    #[test]
+    fn writes_a_table() {
+        let file = file(&["a", "b"], &[&["1", "2"]]);
+
+        assert_eq!(
+            file.to_html(),
+            "<table>\n  <thead>\n    <tr><th>a</th><th>b</th></tr>\n  </thead>\n  \
+             <tbody>\n    <tr><td>1</td><td>2</td></tr>\n  </tbody>\n</table>\n"
+        );
+    }
+
+    #[test]
+    fn escapes_cells_and_heads() {
+        let file = file(
+            &["<b>"],
+            &[&["<script>alert('x')</script>"], &["a & \"b\"\r\nc"]],
+        );
+        let html = file.to_html();
+
+        assert!(html.contains("<th>&lt;b&gt;</th>"), "{html}");
+        assert!(
+            html.contains("<td>&lt;script&gt;alert(&#39;x&#39;)&lt;/script&gt;</td>"),
+            "{html}"
+        );
+        assert!(
+            html.contains("<td>a &amp; &quot;b&quot;<br>c</td>"),
+            "{html}"
+        );
+        assert!(!html.contains("<script>"));
+    }
+
+    #[test]
+    fn escapes_class_and_caption() {
+        let options = HtmlOptions {
+            class: Some("x\" onclick=\"alert(1)".into()),
+            caption: Some("</caption><script>".into()),
+            ..HtmlOptions::default()
+        };
+        let html = file(&["a"], &[]).to_html_with(&options);
+
+        assert!(html.starts_with(
+            "<table class=\"x&quot; onclick=&quot;alert(1)\">\n  \
+             <caption>&lt;/caption&gt;&lt;script&gt;</caption>\n"
+        ));
+    }
+
+    #[test]
+    fn stripes_and_limits_rows() {
+        let file = file(&["n"], &[&["1"], &["2"], &["3"], &["4"]]);
+        let options = HtmlOptions {
+            striped: true,
+            max_rows: Some(2),
+            ..HtmlOptions::default()
+        };
+        let html = file.to_html_with(&options);
+
+        assert!(html.contains("<tr class=\"odd\"><td>1</td></tr>"));
+        assert!(html.contains("<tr class=\"even\"><td>2</td></tr>"));
+        assert!(!html.contains("<td>3</td>"));
+        assert!(html.contains("<tr><td colspan=\"1\">2 more rows</td></tr>"));
+    }
+}
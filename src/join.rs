@@ -0,0 +1,376 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{CsvError, CsvFile, CsvRow};
+
+/**
+ * Join Kind
+ *
+ * Which rows a join keeps.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JoinKind {
+    /// Rows with a match on both sides.
+    #[default]
+    Inner,
+    /// Every left row, with right cells when matched.
+    Left,
+    /// Every right row, with left cells when matched.
+    Right,
+    /// Every row of both sides.
+    Full,
+    /// Left rows with a match, left columns only.
+    Semi,
+    /// Left rows without a match, left columns only.
+    Anti,
+}
+
+/**
+ * Join Options
+ *
+ * Options for joining two files.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinOptions {
+    pub kind: JoinKind,
+    /// Suffixes added to non-key heads found on both sides.
+    pub suffixes: (String, String),
+}
+
+impl Default for JoinOptions {
+    fn default() -> Self {
+        Self {
+            kind: JoinKind::Inner,
+            suffixes: ("_left".to_string(), "_right".to_string()),
+        }
+    }
+}
+
+impl CsvFile {
+    /**
+     * Join
+     *
+     * Join with `other` on the named key columns, which both files must have.
+     */
+    pub fn join(&self, other: &CsvFile, on: &[&str], kind: JoinKind) -> Result<CsvFile, CsvError> {
+        self.join_with(
+            other,
+            on,
+            &JoinOptions {
+                kind,
+                ..JoinOptions::default()
+            },
+        )
+    }
+
+    /**
+     * Join With
+     *
+     * Join with `other` using the given options. The result has every left
+     * column followed by the right non-key columns. Cells of an unmatched
+     * side are empty. Fails with `DuplicateColumn` when suffixing leaves two
+     * heads with the same name.
+     */
+    pub fn join_with(
+        &self,
+        other: &CsvFile,
+        on: &[&str],
+        options: &JoinOptions,
+    ) -> Result<CsvFile, CsvError> {
+        let left_keys = on
+            .iter()
+            .map(|name| self.column_index(name))
+            .collect::<Result<Vec<usize>, CsvError>>()?;
+        let right_keys = on
+            .iter()
+            .map(|name| other.column_index(name))
+            .collect::<Result<Vec<usize>, CsvError>>()?;
+
+        let mut matches: HashMap<Vec<&str>, Vec<usize>> = HashMap::new();
+        for (index, row) in other.rows.iter().enumerate() {
+            matches
+                .entry(key(row, &right_keys))
+                .or_default()
+                .push(index);
+        }
+
+        let mut file = CsvFile::new();
+        file.shape = self.shape.clone();
+
+        if matches!(options.kind, JoinKind::Semi | JoinKind::Anti) {
+            file.heads = self.heads.clone();
+            let keep_matched = options.kind == JoinKind::Semi;
+
+            file.rows = self
+                .rows
+                .iter()
+                .filter(|row| matches.contains_key(&key(row, &left_keys)) == keep_matched)
+                .cloned()
+                .collect();
+
+            return Ok(file);
+        }
+
+        // Right columns that aren't keys.
+        let right_rest = (0..other.heads.len())
+            .filter(|position| !right_keys.contains(position))
+            .collect::<Vec<usize>>();
+
+        let (left_suffix, right_suffix) = &options.suffixes;
+        file.heads = self
+            .heads
+            .iter()
+            .enumerate()
+            .map(|(position, head)| {
+                let collides = !left_keys.contains(&position)
+                    && right_rest.iter().any(|&i| other.heads[i] == *head);
+
+                if collides {
+                    format!("{head}{left_suffix}")
+                } else {
+                    head.clone()
+                }
+            })
+            .collect();
+        file.heads.extend(right_rest.iter().map(|&position| {
+            let head = &other.heads[position];
+
+            if self.head_pos(head).is_some() {
+                format!("{head}{right_suffix}")
+            } else {
+                head.clone()
+            }
+        }));
+
+        // A suffixed head may already be taken, e.g. `name_right` on the left.
+        let mut seen = HashSet::new();
+        if let Some(head) = file.heads.iter().find(|head| !seen.insert(head.as_str())) {
+            return Err(CsvError::DuplicateColumn(head.clone()));
+        }
+
+        let joined = |left: Option<&CsvRow>, right: Option<&CsvRow>| -> CsvRow {
+            let mut row = match left {
+                Some(left) => {
+                    let mut row = left.clone();
+                    row.resize(self.heads.len(), String::new());
+                    row
+                }
+                None => vec![String::new(); self.heads.len()],
+            };
+
+            if let Some(right) = right {
+                // Unmatched right rows still carry their keys.
+                if left.is_none() {
+                    for (&l, &r) in left_keys.iter().zip(right_keys.iter()) {
+                        row[l] = right.get(r).cloned().unwrap_or_default();
+                    }
+                }
+
+                row.extend(
+                    right_rest
+                        .iter()
+                        .map(|&position| right.get(position).cloned().unwrap_or_default()),
+                );
+            } else {
+                row.resize(file.heads.len(), String::new());
+            }
+
+            row
+        };
+
+        match options.kind {
+            JoinKind::Right => {
+                let mut left_matches: HashMap<Vec<&str>, Vec<usize>> = HashMap::new();
+                for (index, row) in self.rows.iter().enumerate() {
+                    left_matches
+                        .entry(key(row, &left_keys))
+                        .or_default()
+                        .push(index);
+                }
+
+                for right in other.rows.iter() {
+                    match left_matches.get(&key(right, &right_keys)) {
+                        Some(lefts) => file.rows.extend(
+                            lefts
+                                .iter()
+                                .map(|&index| joined(Some(&self.rows[index]), Some(right))),
+                        ),
+                        None => file.rows.push(joined(None, Some(right))),
+                    }
+                }
+            }
+            _ => {
+                let mut matched = vec![false; other.rows.len()];
+
+                for left in self.rows.iter() {
+                    match matches.get(&key(left, &left_keys)) {
+                        Some(rights) => {
+                            for &index in rights {
+                                matched[index] = true;
+                                file.rows.push(joined(Some(left), Some(&other.rows[index])));
+                            }
+                        }
+                        None if options.kind != JoinKind::Inner => {
+                            file.rows.push(joined(Some(left), None))
+                        }
+                        None => {}
+                    }
+                }
+
+                if options.kind == JoinKind::Full {
+                    for (index, right) in other.rows.iter().enumerate() {
+                        if !matched[index] {
+                            file.rows.push(joined(None, Some(right)));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(file)
+    }
+}
+
+/**
+ * Key
+ *
+ * Key cells of a row. Missing cells read as empty.
+ */
+fn key<'a>(row: &'a CsvRow, positions: &[usize]) -> Vec<&'a str> {
+    positions
+        .iter()
+        .map(|&position| row.get(position).map_or("", String::as_str))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(heads: &[&str], rows: &[&[&str]]) -> CsvFile {
+        let mut file = CsvFile::new();
+        for head in heads {
+            file.push_head(head);
+        }
+        for row in rows {
+            file.push_row(row).unwrap();
+        }
+        file
+    }
+
+    fn people() -> CsvFile {
+        file(
+            &["id", "name"],
+            &[&["1", "ann"], &["2", "bob"], &["3", "cy"]],
+        )
+    }
+
+    fn orders() -> CsvFile {
+        file(
+            &["id", "item"],
+            &[&["1", "pen"], &["3", "ink"], &["3", "pad"], &["4", "cup"]],
+        )
+    }
+
+    fn join(kind: JoinKind) -> CsvFile {
+        people().join(&orders(), &["id"], kind).unwrap()
+    }
+
+    #[test]
+    fn inner_keeps_matches() {
+        let file = join(JoinKind::Inner);
+
+        assert_eq!(file.heads(), &["id", "name", "item"]);
+        assert_eq!(
+            file.rows(),
+            &[
+                vec!["1", "ann", "pen"],
+                vec!["3", "cy", "ink"],
+                vec!["3", "cy", "pad"],
+            ]
+        );
+    }
+
+    #[test]
+    fn left_keeps_every_left_row() {
+        assert_eq!(
+            join(JoinKind::Left).rows(),
+            &[
+                vec!["1", "ann", "pen"],
+                vec!["2", "bob", ""],
+                vec!["3", "cy", "ink"],
+                vec!["3", "cy", "pad"],
+            ]
+        );
+    }
+
+    #[test]
+    fn right_keeps_every_right_row() {
+        assert_eq!(
+            join(JoinKind::Right).rows(),
+            &[
+                vec!["1", "ann", "pen"],
+                vec!["3", "cy", "ink"],
+                vec!["3", "cy", "pad"],
+                vec!["4", "", "cup"],
+            ]
+        );
+    }
+
+    #[test]
+    fn full_keeps_every_row() {
+        assert_eq!(
+            join(JoinKind::Full).rows(),
+            &[
+                vec!["1", "ann", "pen"],
+                vec!["2", "bob", ""],
+                vec!["3", "cy", "ink"],
+                vec!["3", "cy", "pad"],
+                vec!["4", "", "cup"],
+            ]
+        );
+    }
+
+    #[test]
+    fn semi_and_anti_keep_left_columns() {
+        let semi = join(JoinKind::Semi);
+        assert_eq!(semi.heads(), &["id", "name"]);
+        assert_eq!(semi.rows(), &[vec!["1", "ann"], vec!["3", "cy"]]);
+
+        let anti = join(JoinKind::Anti);
+        assert_eq!(anti.heads(), &["id", "name"]);
+        assert_eq!(anti.rows(), &[vec!["2", "bob"]]);
+    }
+
+    #[test]
+    fn shared_heads_are_suffixed() {
+        let left = file(&["id", "name"], &[&["1", "ann"]]);
+        let right = file(&["id", "name"], &[&["1", "bob"]]);
+        let options = JoinOptions {
+            suffixes: ("_a".into(), "_b".into()),
+            ..JoinOptions::default()
+        };
+
+        let joined = left.join_with(&right, &["id"], &options).unwrap();
+        assert_eq!(joined.heads(), &["id", "name_a", "name_b"]);
+        assert_eq!(joined.rows(), &[vec!["1", "ann", "bob"]]);
+    }
+
+    #[test]
+    fn suffixes_must_not_collide() {
+        let left = file(&["id", "name", "name_right"], &[]);
+        let right = file(&["id", "name"], &[]);
+
+        assert!(matches!(
+            left.join(&right, &["id"], JoinKind::Inner),
+            Err(CsvError::DuplicateColumn(head)) if head == "name_right"
+        ));
+    }
+
+    #[test]
+    fn unknown_keys_fail() {
+        assert!(matches!(
+            people().join(&orders(), &["name"], JoinKind::Inner),
+            Err(CsvError::UnknownColumn(_))
+        ));
+    }
+}
@@ -0,0 +1,1025 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::{CsvError, CsvFile, CsvRow};
+
+/// Deepest nesting `from_json` accepts, to keep recursion bounded.
+const MAX_DEPTH: usize = 128;
+
+/**
+ * JSON Layout
+ *
+ * Shape of the JSON written by `to_json`.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum JsonLayout {
+    /// Array of objects keyed by head, e.g. `[{"a": "1"}]`.
+    #[default]
+    Records,
+    /// Object of column arrays, e.g. `{"a": ["1"]}`.
+    Columns,
+    /// Array of arrays, heads first, e.g. `[["a"], ["1"]]`.
+    Rows,
+}
+
+/**
+ * JSON Options
+ *
+ * Options for writing JSON.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct JsonOptions {
+    pub layout: JsonLayout,
+    /// Write numbers and `true`/`false` unquoted and empty cells as `null`.
+    /// Otherwise every cell is a string.
+    pub typed: bool,
+}
+
+/**
+ * Value
+ *
+ * Parsed JSON value. Numbers keep their source text.
+ */
+#[derive(Debug, Clone, PartialEq)]
+enum Value {
+    Null,
+    Bool(bool),
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Null => "null",
+            Self::Bool(_) => "a boolean",
+            Self::Number(_) => "a number",
+            Self::String(_) => "a string",
+            Self::Array(_) => "an array",
+            Self::Object(_) => "an object",
+        }
+    }
+
+    /**
+     * Into Cell
+     *
+     * Cell text of a value. `null` is empty and arrays and objects are
+     * kept as compact JSON.
+     */
+    fn into_cell(self) -> String {
+        match self {
+            Self::Null => String::new(),
+            Self::Bool(value) => value.to_string(),
+            Self::Number(number) => number,
+            Self::String(text) => text,
+            value => {
+                let mut json = String::new();
+                push_value(&mut json, &value);
+                json
+            }
+        }
+    }
+}
+
+impl CsvFile {
+    /**
+     * To JSON
+     *
+     * Array of objects keyed by head, with every cell as a string.
+     */
+    pub fn to_json(&self) -> String {
+        self.to_json_with(&JsonOptions::default())
+    }
+
+    /**
+     * To JSON With
+     *
+     * JSON in the given layout. Each row is written on its own line.
+     */
+    pub fn to_json_with(&self, options: &JsonOptions) -> String {
+        let mut json = String::new();
+
+        match options.layout {
+            JsonLayout::Records => {
+                json.push('[');
+                for (index, row) in self.rows.iter().enumerate() {
+                    json.push_str(if index > 0 { ",\n  " } else { "\n  " });
+                    self.push_record(&mut json, row, options.typed);
+                }
+                json.push_str(if self.rows.is_empty() { "]" } else { "\n]" });
+            }
+            JsonLayout::Columns => {
+                json.push('{');
+                for (index, head) in self.heads.iter().enumerate() {
+                    json.push_str(if index > 0 { ",\n  " } else { "\n  " });
+                    push_string(&mut json, head);
+                    json.push_str(": ");
+
+                    let cells = self.rows.iter().map(|row| row.get(index));
+                    push_array(&mut json, cells, options.typed);
+                }
+                json.push_str(if self.heads.is_empty() { "}" } else { "\n}" });
+            }
+            JsonLayout::Rows => {
+                json.push_str("[\n  ");
+                push_array(&mut json, self.heads.iter().map(Some), false);
+                for row in &self.rows {
+                    json.push_str(",\n  ");
+                    push_array(&mut json, row.iter().map(Some), options.typed);
+                }
+                json.push_str("\n]");
+            }
+        }
+
+        json
+    }
+
+    /**
+     * To JSON Lines
+     *
+     * One object per row, keyed by head, with every cell as a string.
+     */
+    pub fn to_jsonl(&self) -> String {
+        self.to_jsonl_with(&JsonOptions::default())
+    }
+
+    /**
+     * To JSON Lines With
+     *
+     * One object per row with the given options. The layout is ignored.
+     * Heads are only written as keys, so a file without rows is empty.
+     */
+    pub fn to_jsonl_with(&self, options: &JsonOptions) -> String {
+        let mut json = String::new();
+
+        for row in &self.rows {
+            self.push_record(&mut json, row, options.typed);
+            json.push('\n');
+        }
+
+        json
+    }
+
+    /**
+     * Write JSON Lines
+     *
+     * Write a JSON Lines file, one object per row.
+     */
+    pub fn write_jsonl<P: AsRef<Path>>(&self, path: P) -> Result<(), CsvError> {
+        self.write_jsonl_with(path, &JsonOptions::default())
+    }
+
+    /**
+     * Write JSON Lines With
+     *
+     * Write a JSON Lines file with the given options. The layout is ignored.
+     */
+    pub fn write_jsonl_with<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: &JsonOptions,
+    ) -> Result<(), CsvError> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        let mut line = String::new();
+
+        for row in &self.rows {
+            line.clear();
+            self.push_record(&mut line, row, options.typed);
+            line.push('\n');
+            writer.write_all(line.as_bytes())?;
+        }
+
+        writer.flush()?;
+        Ok(())
+    }
+
+    /**
+     * From JSON
+     *
+     * Read any layout `to_json` writes. Nested objects are flattened into
+     * dotted heads, e.g. `address.city`, and heads appear in the order they
+     * are first seen. Missing keys and `null` are empty cells; arrays inside
+     * records are kept as JSON text.
+     *
+     * Two files don't read back as written: records of a file without rows
+     * are `[]`, which has no heads, and rows of a file without heads start
+     * with `[]`, so any non-empty row fails with `ShapeMismatch`.
+     */
+    pub fn from_json(json: &str) -> Result<CsvFile, CsvError> {
+        let mut parser = Parser::new(json, 1);
+
+        let file = match parser.peek() {
+            Some(b'[') => {
+                parser.bump();
+                let mut records = Records::default();
+                let mut rows: Option<Vec<CsvRow>> = None;
+
+                while !parser.eat(b']') {
+                    if records.len() + rows.as_ref().map_or(0, Vec::len) > 0 {
+                        parser.expect(b',')?;
+                    }
+
+                    let at = parser.position();
+                    match (parser.parse_value()?, &mut rows) {
+                        (Value::Object(members), None) => records.push(members),
+                        (Value::Array(cells), rows) if records.len() == 0 => rows
+                            .get_or_insert_with(Vec::new)
+                            .push(cells.into_iter().map(Value::into_cell).collect()),
+                        (value, rows) => {
+                            let expected = match (records.len(), rows) {
+                                (0, None) => "an object or array",
+                                (_, None) => "an object",
+                                (_, Some(_)) => "an array",
+                            };
+                            return Err(parser.error_at(
+                                at,
+                                format!("expected {expected}, found {}", value.kind()),
+                            ));
+                        }
+                    }
+                }
+
+                match rows {
+                    Some(rows) => from_rows(rows)?,
+                    None => records.finish(),
+                }
+            }
+            Some(b'{') => {
+                parser.bump();
+                let mut file = CsvFile::new();
+
+                while !parser.eat(b'}') {
+                    if !file.heads.is_empty() {
+                        parser.expect(b',')?;
+                    }
+
+                    let head = parser.parse_key()?;
+                    let at = parser.position();
+                    let Value::Array(cells) = parser.parse_value()? else {
+                        return Err(parser.error_at(at, "expected a column array"));
+                    };
+
+                    let index = file.heads.len();
+                    file.heads.push(head);
+                    if file.rows.len() < cells.len() {
+                        file.rows.resize(cells.len(), vec![String::new(); index]);
+                    }
+                    for (row, cell) in file.rows.iter_mut().zip(cells) {
+                        row.push(cell.into_cell());
+                    }
+                    for row in &mut file.rows {
+                        row.resize(index + 1, String::new());
+                    }
+                }
+
+                file
+            }
+            _ => return Err(parser.error("expected an array or object")),
+        };
+
+        parser.finish()?;
+        Ok(file)
+    }
+
+    /**
+     * From JSON Lines
+     *
+     * Read one object per line, flattened like `from_json`. Blank lines are
+     * skipped.
+     */
+    pub fn from_jsonl(json: &str) -> Result<CsvFile, CsvError> {
+        let mut records = Records::default();
+
+        for (index, line) in json.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let mut parser = Parser::new(line, index as u64 + 1);
+            let at = parser.position();
+            match parser.parse_value()? {
+                Value::Object(members) => records.push(members),
+                value => {
+                    return Err(
+                        parser.error_at(at, format!("expected an object, found {}", value.kind()))
+                    )
+                }
+            }
+            parser.finish()?;
+        }
+
+        Ok(records.finish())
+    }
+
+    /**
+     * Push Record
+     *
+     * Push a row as an object keyed by head. Cells without a head are left out.
+     */
+    fn push_record(&self, json: &mut String, row: &[String], typed: bool) {
+        json.push('{');
+        for (index, head) in self.heads.iter().enumerate() {
+            if index > 0 {
+                json.push_str(", ");
+            }
+            push_string(json, head);
+            json.push_str(": ");
+            push_cell(json, row.get(index).map_or("", String::as_str), typed);
+        }
+        json.push('}');
+    }
+}
+
+/**
+ * Records
+ *
+ * Collects flattened objects into heads and rows.
+ */
+#[derive(Debug, Default)]
+struct Records {
+    heads: Vec<String>,
+    index: HashMap<String, usize>,
+    rows: Vec<CsvRow>,
+}
+
+impl Records {
+    fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn push(&mut self, members: Vec<(String, Value)>) {
+        let mut fields = Vec::new();
+        for (key, value) in members {
+            flatten(key, value, &mut fields);
+        }
+
+        let mut row = vec![String::new(); self.heads.len()];
+        for (key, cell) in fields {
+            let index = match self.index.get(&key) {
+                Some(index) => *index,
+                None => {
+                    self.heads.push(key.clone());
+                    self.index.insert(key, self.heads.len() - 1);
+                    row.push(String::new());
+                    self.heads.len() - 1
+                }
+            };
+            row[index] = cell;
+        }
+
+        self.rows.push(row);
+    }
+
+    fn finish(mut self) -> CsvFile {
+        for row in &mut self.rows {
+            row.resize(self.heads.len(), String::new());
+        }
+
+        CsvFile {
+            heads: self.heads,
+            rows: self.rows,
+            ..CsvFile::default()
+        }
+    }
+}
+
+/**
+ * Flatten
+ *
+ * Push the cells of a value under `key`, joining nested keys with `.`.
+ */
+fn flatten(key: String, value: Value, fields: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(members) if !members.is_empty() => {
+            for (name, value) in members {
+                flatten(format!("{key}.{name}"), value, fields);
+            }
+        }
+        value => fields.push((key, value.into_cell())),
+    }
+}
+
+/**
+ * From Rows
+ *
+ * File from arrays of cells, the first being the heads. Short rows are
+ * padded with empty cells.
+ */
+fn from_rows(mut rows: Vec<CsvRow>) -> Result<CsvFile, CsvError> {
+    let heads = rows.remove(0);
+
+    for row in &mut rows {
+        if row.len() > heads.len() {
+            return Err(CsvError::ShapeMismatch {
+                expected: heads.len(),
+                found: row.len(),
+            });
+        }
+        row.resize(heads.len(), String::new());
+    }
+
+    Ok(CsvFile {
+        heads,
+        rows,
+        ..CsvFile::default()
+    })
+}
+
+/**
+ * Push Array
+ *
+ * Push cells as a JSON array. Missing cells are empty.
+ */
+fn push_array<'a>(json: &mut String, cells: impl Iterator<Item = Option<&'a String>>, typed: bool) {
+    json.push('[');
+    for (index, cell) in cells.enumerate() {
+        if index > 0 {
+            json.push_str(", ");
+        }
+        push_cell(json, cell.map_or("", String::as_str), typed);
+    }
+    json.push(']');
+}
+
+/**
+ * Push Cell
+ *
+ * Push a cell as a string, or as a typed value when `typed` is set.
+ */
+fn push_cell(json: &mut String, cell: &str, typed: bool) {
+    if !typed {
+        push_string(json, cell);
+        return;
+    }
+
+    match cell {
+        "" => json.push_str("null"),
+        "true" | "false" => json.push_str(cell),
+        _ if number_len(cell.as_bytes()) == Some(cell.len()) => json.push_str(cell),
+        _ => push_string(json, cell),
+    }
+}
+
+/**
+ * Push String
+ *
+ * Push text as a quoted JSON string.
+ */
+fn push_string(json: &mut String, text: &str) {
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c < ' ' => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+/**
+ * Push Value
+ *
+ * Push a value as compact JSON.
+ */
+fn push_value(json: &mut String, value: &Value) {
+    match value {
+        Value::Null => json.push_str("null"),
+        Value::Bool(value) => json.push_str(&value.to_string()),
+        Value::Number(number) => json.push_str(number),
+        Value::String(text) => push_string(json, text),
+        Value::Array(values) => {
+            json.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                push_value(json, value);
+            }
+            json.push(']');
+        }
+        Value::Object(members) => {
+            json.push('{');
+            for (index, (key, value)) in members.iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                push_string(json, key);
+                json.push(':');
+                push_value(json, value);
+            }
+            json.push('}');
+        }
+    }
+}
+
+/**
+ * Number Len
+ *
+ * Length of the JSON number at the start of `bytes`, if any.
+ */
+fn number_len(bytes: &[u8]) -> Option<usize> {
+    let digits = |from: usize| {
+        bytes[from..]
+            .iter()
+            .take_while(|byte| byte.is_ascii_digit())
+            .count()
+    };
+    let mut i = usize::from(bytes.first() == Some(&b'-'));
+
+    match bytes.get(i) {
+        Some(b'0') => i += 1,
+        Some(b'1'..=b'9') => i += digits(i),
+        _ => return None,
+    }
+
+    if bytes.get(i) == Some(&b'.') {
+        let fraction = digits(i + 1);
+        if fraction == 0 {
+            return None;
+        }
+        i += 1 + fraction;
+    }
+
+    if matches!(bytes.get(i), Some(b'e' | b'E')) {
+        i += 1;
+        if matches!(bytes.get(i), Some(b'+' | b'-')) {
+            i += 1;
+        }
+        let exponent = digits(i);
+        if exponent == 0 {
+            return None;
+        }
+        i += exponent;
+    }
+
+    Some(i)
+}
+
+/**
+ * Parser
+ *
+ * Recursive descent JSON parser that tracks lines for error messages.
+ */
+#[derive(Debug)]
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    line: u64,
+    line_start: usize,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str, line: u64) -> Self {
+        Self {
+            text,
+            pos: 0,
+            line,
+            line_start: 0,
+            depth: 0,
+        }
+    }
+
+    /**
+     * Position
+     *
+     * Line and column of the next value, both starting at 1.
+     */
+    fn position(&mut self) -> (u64, u64) {
+        self.skip_whitespace();
+        let mut end = self.pos.min(self.text.len());
+        while !self.text.is_char_boundary(end) {
+            end -= 1;
+        }
+        let column = self.text[self.line_start..end].chars().count() + 1;
+
+        (self.line, column as u64)
+    }
+
+    fn error(&mut self, message: impl Into<String>) -> CsvError {
+        let at = self.position();
+        self.error_at(at, message)
+    }
+
+    fn error_at(&self, (line, column): (u64, u64), message: impl Into<String>) -> CsvError {
+        CsvError::InvalidJson {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(&byte) = self.text.as_bytes().get(self.pos) {
+            match byte {
+                b' ' | b'\t' | b'\r' => {}
+                b'\n' => {
+                    self.line += 1;
+                    self.line_start = self.pos + 1;
+                }
+                _ => break,
+            }
+            self.pos += 1;
+        }
+    }
+
+    /**
+     * Peek
+     *
+     * Next byte after whitespace.
+     */
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn bump(&mut self) {
+        self.pos += 1;
+    }
+
+    /**
+     * Eat
+     *
+     * Skip `byte` if it's next.
+     */
+    fn eat(&mut self, byte: u8) -> bool {
+        let found = self.peek() == Some(byte);
+        if found {
+            self.bump();
+        }
+        found
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), CsvError> {
+        if self.eat(byte) {
+            return Ok(());
+        }
+
+        let message = match self.text[self.pos..].chars().next() {
+            Some(found) => format!("expected '{}', found {found:?}", byte as char),
+            None => format!("expected '{}', found end of input", byte as char),
+        };
+        Err(self.error(message))
+    }
+
+    /**
+     * Finish
+     *
+     * Fail unless only whitespace is left.
+     */
+    fn finish(&mut self) -> Result<(), CsvError> {
+        match self.peek() {
+            Some(_) => Err(self.error("unexpected text after the value")),
+            None => Ok(()),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value, CsvError> {
+        match self.peek() {
+            Some(b'{') => self.parse_nested(b'}'),
+            Some(b'[') => self.parse_nested(b']'),
+            Some(b'"') => Ok(Value::String(self.parse_string()?)),
+            Some(b't') => self.parse_literal("true", Value::Bool(true)),
+            Some(b'f') => self.parse_literal("false", Value::Bool(false)),
+            Some(b'n') => self.parse_literal("null", Value::Null),
+            Some(_) => {
+                let len = number_len(&self.text.as_bytes()[self.pos..])
+                    .ok_or_else(|| self.error("expected a value"))?;
+                let number = self.text[self.pos..self.pos + len].to_string();
+                self.pos += len;
+                Ok(Value::Number(number))
+            }
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    /**
+     * Parse Nested
+     *
+     * Parse an object or array, whose closing byte is `close`.
+     */
+    fn parse_nested(&mut self, close: u8) -> Result<Value, CsvError> {
+        if self.depth == MAX_DEPTH {
+            return Err(self.error("nested too deeply"));
+        }
+        self.depth += 1;
+        self.bump();
+
+        let mut values = Vec::new();
+        let mut members = Vec::new();
+        while !self.eat(close) {
+            if !values.is_empty() || !members.is_empty() {
+                self.expect(b',')?;
+            }
+
+            if close == b'}' {
+                let key = self.parse_key()?;
+                members.push((key, self.parse_value()?));
+            } else {
+                values.push(self.parse_value()?);
+            }
+        }
+
+        self.depth -= 1;
+        Ok(match close {
+            b'}' => Value::Object(members),
+            _ => Value::Array(values),
+        })
+    }
+
+    /**
+     * Parse Key
+     *
+     * Parse an object key and the `:` after it.
+     */
+    fn parse_key(&mut self) -> Result<String, CsvError> {
+        if self.peek() != Some(b'"') {
+            return Err(self.error("expected a string key"));
+        }
+
+        let key = self.parse_string()?;
+        self.expect(b':')?;
+        Ok(key)
+    }
+
+    fn parse_literal(&mut self, literal: &str, value: Value) -> Result<Value, CsvError> {
+        if !self.text[self.pos..].starts_with(literal) {
+            return Err(self.error("expected a value"));
+        }
+
+        self.pos += literal.len();
+        Ok(value)
+    }
+
+    /**
+     * Parse String
+     *
+     * Parse a quoted string, resolving escapes.
+     */
+    fn parse_string(&mut self) -> Result<String, CsvError> {
+        self.bump();
+        let mut text = String::new();
+
+        loop {
+            let rest = &self.text[self.pos..];
+            let end = rest
+                .find(|c: char| c == '"' || c == '\\' || c < ' ')
+                .ok_or_else(|| self.error("unterminated string"))?;
+            text.push_str(&rest[..end]);
+            self.pos += end;
+
+            match self.text.as_bytes()[self.pos] {
+                b'"' => {
+                    self.bump();
+                    return Ok(text);
+                }
+                b'\\' => {
+                    self.bump();
+                    text.push(self.parse_escape()?);
+                }
+                _ => return Err(self.error("control character in string")),
+            }
+        }
+    }
+
+    /**
+     * Parse Escape
+     *
+     * Parse the escape after a `\`, including `\u` surrogate pairs.
+     */
+    fn parse_escape(&mut self) -> Result<char, CsvError> {
+        let Some(escaped) = self.text[self.pos..].chars().next() else {
+            return Err(self.error("unterminated string"));
+        };
+        if !matches!(
+            escaped,
+            '"' | '\\' | '/' | 'b' | 'f' | 'n' | 'r' | 't' | 'u'
+        ) {
+            return Err(self.error("invalid escape"));
+        }
+        self.pos += escaped.len_utf8();
+
+        let c = match escaped {
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let high = self.parse_hex()?;
+                let code = if (0xD800..0xDC00).contains(&high)
+                    && self.text[self.pos..].starts_with("\\u")
+                {
+                    self.pos += 2;
+                    let low = self.parse_hex()?;
+                    if !(0xDC00..0xE000).contains(&low) {
+                        return Err(self.error("invalid surrogate pair"));
+                    }
+                    0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                } else {
+                    high
+                };
+
+                char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))?
+            }
+            escaped => escaped,
+        };
+
+        Ok(c)
+    }
+
+    fn parse_hex(&mut self) -> Result<u32, CsvError> {
+        let hex = self
+            .text
+            .get(self.pos..self.pos + 4)
+            .filter(|hex| hex.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+
+        self.pos += 4;
+        Ok(hex)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> CsvFile {
+        let mut file = CsvFile::new();
+        for head in ["name", "age", "note"] {
+            file.push_head(head);
+        }
+        file.push_row(&["Ann \"A\"", "30", "line\n\ttab \u{1} é 😀"])
+            .unwrap();
+        file.push_row(&["Bob", "", "true"]).unwrap();
+        file
+    }
+
+    fn error(json: &str) -> (u64, u64, String) {
+        match CsvFile::from_json(json) {
+            Err(CsvError::InvalidJson {
+                line,
+                column,
+                message,
+            }) => (line, column, message),
+            other => panic!("expected InvalidJson for {json:?}, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn every_layout_round_trips() {
+        let file = sample();
+
+        for layout in [JsonLayout::Records, JsonLayout::Columns, JsonLayout::Rows] {
+            for typed in [false, true] {
+                let json = file.to_json_with(&JsonOptions { layout, typed });
+                assert_eq!(CsvFile::from_json(&json).unwrap(), file, "{json}");
+            }
+        }
+
+        assert_eq!(CsvFile::from_jsonl(&file.to_jsonl()).unwrap(), file);
+
+        // Heads without rows are lost in records and JSON Lines.
+        let mut heads_only = CsvFile::new();
+        heads_only.push_head("name");
+
+        let json = heads_only.to_json();
+        assert_eq!(json, "[]");
+        assert_eq!(CsvFile::from_json(&json).unwrap(), CsvFile::new());
+        assert_eq!(heads_only.to_jsonl(), "");
+        assert_eq!(CsvFile::from_jsonl("").unwrap(), CsvFile::new());
+
+        // Rows without heads start with an empty head array.
+        let mut headless = CsvFile::new();
+        headless.push_row(&["a", "b"]).unwrap();
+
+        let json = headless.to_json_with(&JsonOptions {
+            layout: JsonLayout::Rows,
+            typed: false,
+        });
+        assert!(matches!(
+            CsvFile::from_json(&json),
+            Err(CsvError::ShapeMismatch {
+                expected: 0,
+                found: 2
+            })
+        ));
+    }
+
+    #[test]
+    fn typed_cells() {
+        let json = sample().to_json_with(&JsonOptions {
+            typed: true,
+            ..JsonOptions::default()
+        });
+
+        assert!(json.contains(r#""age": 30"#));
+        assert!(json.contains(r#""age": null"#));
+        assert!(json.contains(r#""note": true"#));
+    }
+
+    #[test]
+    fn nested_objects_flatten_into_dotted_heads() {
+        let file = CsvFile::from_json(
+            r#"[{"id": 1, "address": {"city": "Oslo", "geo": {"lat": 1.5e3}}, "tags": ["a", null]},
+                {"id": 2, "extra": "\ud83d\ude00\u00e9"}]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            file.heads(),
+            &["id", "address.city", "address.geo.lat", "tags", "extra"]
+        );
+        assert_eq!(
+            file.rows(),
+            &[
+                vec!["1", "Oslo", "1.5e3", r#"["a",null]"#, ""],
+                vec!["2", "", "", "", "😀é"],
+            ]
+        );
+    }
+
+    #[test]
+    fn json_lines_report_their_line() {
+        let err = CsvFile::from_jsonl("{\"a\": 1}\n\n[1]\n").unwrap_err();
+
+        assert!(matches!(
+            err,
+            CsvError::InvalidJson {
+                line: 3,
+                column: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn truncated_escapes_are_errors() {
+        assert_eq!(error("[\"\\"), (1, 4, "unterminated string".into()));
+        assert_eq!(error("[\"\\u12"), (1, 5, "invalid unicode escape".into()));
+        assert!(CsvFile::from_jsonl("{\"a\": \"\\").is_err());
+    }
+
+    #[test]
+    fn invalid_escapes_are_errors() {
+        assert_eq!(error("[\"\\é\"]"), (1, 4, "invalid escape".into()));
+        assert_eq!(error("[\"\\x\"]"), (1, 4, "invalid escape".into()));
+        assert_eq!(
+            error("[\"\\u+123\"]"),
+            (1, 5, "invalid unicode escape".into())
+        );
+        assert_eq!(error("[\"\\udc00\"]").2, "invalid unicode escape");
+        assert_eq!(error("[\"\\ud83d\\u0041\"]").2, "invalid surrogate pair");
+        assert!(matches!(
+            CsvFile::from_jsonl("{\"a\":\"\\é\"}"),
+            Err(CsvError::InvalidJson { .. })
+        ));
+    }
+
+    #[test]
+    fn every_prefix_of_valid_json_is_an_error_not_a_panic() {
+        let json = r#"[{"a": "\u00e9\ud83d\ude00 é\"\\", "b": {"c": [1.5e-3, -0, true, null]}}]"#;
+
+        for (end, _) in json.char_indices().skip(1) {
+            assert!(
+                CsvFile::from_json(&json[..end]).is_err(),
+                "{}",
+                &json[..end]
+            );
+        }
+        assert!(CsvFile::from_json(json).is_ok());
+
+        let object = &json[1..json.len() - 1];
+        for (end, _) in object.char_indices().skip(1) {
+            assert!(CsvFile::from_jsonl(&object[..end]).is_err());
+        }
+        assert!(CsvFile::from_jsonl(object).is_ok());
+    }
+
+    #[test]
+    fn structural_errors() {
+        assert_eq!(error("").2, "expected an array or object");
+        assert_eq!(
+            error("[1]").2,
+            "expected an object or array, found a number"
+        );
+        assert_eq!(error("[{}, [1]]").2, "expected an object, found an array");
+        assert_eq!(error("{\"a\": 1}").2, "expected a column array");
+        assert_eq!(error("[{}] x").2, "unexpected text after the value");
+        assert_eq!(
+            error("[{\"a\":1},\n {\"a\": tru}]"),
+            (2, 8, "expected a value".into())
+        );
+        assert_eq!(error(&"[".repeat(1000)).2, "nested too deeply");
+    }
+}
@@ -1,9 +1,47 @@
-use std::fmt;
 use std::fs::File;
-use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::io::{Read, Write};
 use std::path::Path;
 
-const CSV_SEP: &str = ",";
+mod column;
+mod concat;
+#[cfg(feature = "serde")]
+mod de;
+mod dedup;
+mod dialect;
+mod display;
+mod error;
+mod filter;
+mod group;
+mod html;
+mod join;
+mod json;
+mod markdown;
+mod reader;
+mod record;
+mod row;
+#[cfg(feature = "serde")]
+mod ser;
+mod shape;
+mod sort;
+mod writer;
+
+pub use column::ColumnValues;
+pub use concat::ConcatOptions;
+pub use dedup::Keep;
+pub use dialect::{Dialect, Escape, Terminator};
+pub use display::{BorderStyle, Table};
+pub use error::{CsvError, Position};
+pub use group::{Agg, Group, GroupBy};
+pub use html::HtmlOptions;
+pub use join::{JoinKind, JoinOptions};
+pub use json::{JsonLayout, JsonOptions};
+pub use markdown::{Align, MarkdownOptions};
+pub use reader::{CsvReader, EmptyPolicy, ReadOptions};
+pub use record::{ByteRecord, StringRecord};
+pub use row::{RowIter, RowRef};
+pub use shape::ShapePolicy;
+pub use sort::{Compare, Nulls, SortKey, SortOrder};
+pub use writer::{CsvWriter, QuoteStyle, WriteOptions};
 
 type CsvCell = String;
 type CsvHead = Vec<CsvCell>;
@@ -14,12 +52,23 @@ type CsvRow = Vec<CsvCell>;
  *
  * CSV file manupulation for Rust Lang.
  */
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct CsvFile {
     heads: CsvHead,
     rows: Vec<CsvRow>,
+    shape: ShapePolicy,
 }
 
+/// Files are equal when their heads and rows are. The shape policy only
+/// affects later changes, so it isn't compared.
+impl PartialEq for CsvFile {
+    fn eq(&self, other: &Self) -> bool {
+        self.heads == other.heads && self.rows == other.rows
+    }
+}
+
+impl Eq for CsvFile {}
+
 impl CsvFile {
     /**
      * New
@@ -30,6 +79,24 @@ impl CsvFile {
         Self::default()
     }
 
+    /**
+     * Shape Policy
+     *
+     * Policy for rows that don't match the heads.
+     */
+    pub fn shape_policy(&self) -> &ShapePolicy {
+        &self.shape
+    }
+
+    /**
+     * Set Shape Policy
+     *
+     * Set the policy used by later row changes. Existing rows are kept as is.
+     */
+    pub fn set_shape_policy(&mut self, shape: ShapePolicy) {
+        self.shape = shape;
+    }
+
     /**
      * Heads
      *
@@ -63,19 +130,7 @@ impl CsvFile {
      * Get all the columns.
      */
     pub fn cols(&self, name: &str) -> Option<CsvRow> {
-        let index = self.head_pos(name)?;
-
-        let cols = self
-            .rows
-            .iter()
-            .map(|row| {
-                if let Some(data) = row.get(index) {
-                    data.to_string()
-                } else {
-                    "".to_string()
-                }
-            })
-            .collect::<Vec<String>>();
+        let cols = self.col(name)?.map(str::to_string).collect();
 
         Some(cols)
     }
@@ -85,8 +140,10 @@ impl CsvFile {
      *
      * Get the row.
      */
-    pub fn row(&self, position: usize) -> Option<&CsvRow> {
-        self.rows.get(position)
+    pub fn row(&self, position: usize) -> Option<RowRef<'_>> {
+        let cells = self.rows.get(position)?;
+
+        Some(RowRef::new(&self.heads, cells, position))
     }
 
     /**
@@ -105,9 +162,16 @@ impl CsvFile {
     /**
      * Push Head
      *
-     * Push new head.
+     * Push new head. Unless the shape policy is `Flexible`, rows under
+     * existing heads get a new empty (or `Pad`) cell.
      */
     pub fn push_head(&mut self, name: &str) {
+        if let (Some(fill), false) = (self.shape.fill(), self.heads.is_empty()) {
+            for row in self.rows.iter_mut() {
+                row.push(fill.clone());
+            }
+        }
+
         self.heads.push(name.to_string());
     }
 
@@ -116,39 +180,64 @@ impl CsvFile {
      *
      * Set head to target position.
      */
-    pub fn set_head(&mut self, position: usize, name: &str) {
-        if let None = self.heads.get(position) {
-            return;
-        }
+    pub fn set_head(&mut self, position: usize, name: &str) -> Result<(), CsvError> {
+        Self::check_index(position, self.heads.len())?;
 
         self.heads[position] = name.to_string();
+
+        Ok(())
     }
 
     /**
      * Insert Head
      *
-     * Insert new head.
+     * Insert new head. Unless the shape policy is `Flexible`, rows under
+     * existing heads get a new empty (or `Pad`) cell at the same position.
      */
-    pub fn insert_head(&mut self, position: usize, name: &str) {
+    pub fn insert_head(&mut self, position: usize, name: &str) -> Result<(), CsvError> {
+        Self::check_index(position, self.heads.len() + 1)?;
+
+        if let (Some(fill), false) = (self.shape.fill(), self.heads.is_empty()) {
+            for row in self.rows.iter_mut() {
+                row.insert(position.min(row.len()), fill.clone());
+            }
+        }
+
         self.heads.insert(position, name.to_string());
+
+        Ok(())
     }
 
     /**
      * Delete Head
      *
-     *
+     * Delete head at target position. Unless the shape policy is
+     * `Flexible`, the cell under it is deleted too.
      */
-    pub fn delete_head(&mut self, position: usize) -> String {
-        self.heads.remove(position)
+    pub fn delete_head(&mut self, position: usize) -> Result<String, CsvError> {
+        Self::check_index(position, self.heads.len())?;
+
+        if self.shape != ShapePolicy::Flexible {
+            self.remove_cells(position);
+        }
+
+        Ok(self.heads.remove(position))
     }
 
     /**
      * Pop Head
      *
-     *
+     * Pop last head. Unless the shape policy is `Flexible`, the cell under
+     * it is popped too.
      */
     pub fn pop_head(&mut self) -> Option<String> {
-        self.heads.pop()
+        let head = self.heads.pop()?;
+
+        if self.shape != ShapePolicy::Flexible {
+            self.remove_cells(self.heads.len());
+        }
+
+        Some(head)
     }
 
     /**
@@ -156,12 +245,13 @@ impl CsvFile {
      *
      * Push new column.
      */
-    pub fn push_col(&mut self, row: usize, value: &str) {
-        if let None = self.rows.get(row) {
-            return;
-        }
+    pub fn push_col(&mut self, row: usize, value: &str) -> Result<(), CsvError> {
+        Self::check_index(row, self.rows.len())?;
+        self.check_width(self.rows[row].len() + 1)?;
 
         self.rows[row].push(value.to_string());
+
+        Ok(())
     }
 
     /**
@@ -169,16 +259,13 @@ impl CsvFile {
      *
      * Set column to target position.
      */
-    pub fn set_col(&mut self, row: usize, col: usize, value: &str) {
-        if let None = self.rows.get(row) {
-            return;
-        }
-
-        if let None = self.rows[row].get(col) {
-            return;
-        }
+    pub fn set_col(&mut self, row: usize, col: usize, value: &str) -> Result<(), CsvError> {
+        Self::check_index(row, self.rows.len())?;
+        Self::check_index(col, self.rows[row].len())?;
 
         self.rows[row][col] = value.to_string();
+
+        Ok(())
     }
 
     /**
@@ -186,8 +273,14 @@ impl CsvFile {
      *
      * Insert new column.
      */
-    pub fn insert_col(&mut self, row: usize, position: usize, value: &str) {
-        self.rows[row].insert(position, value.to_string())
+    pub fn insert_col(&mut self, row: usize, position: usize, value: &str) -> Result<(), CsvError> {
+        Self::check_index(row, self.rows.len())?;
+        Self::check_index(position, self.rows[row].len() + 1)?;
+        self.check_width(self.rows[row].len() + 1)?;
+
+        self.rows[row].insert(position, value.to_string());
+
+        Ok(())
     }
 
     /**
@@ -195,31 +288,29 @@ impl CsvFile {
      *
      * Delete all columns.
      */
-    pub fn delete_col(&mut self, position: usize) {
-        self.delete_head(position);
+    pub fn delete_col(&mut self, position: usize) -> Result<(), CsvError> {
+        Self::check_index(position, self.heads.len())?;
 
-        // Rows
-        for row in self.rows.iter_mut() {
-            if let None = row.get(position) {
-                continue;
-            }
+        self.heads.remove(position);
+        self.remove_cells(position);
 
-            row.remove(position);
-        }
+        Ok(())
     }
 
     /**
      * Pop Col
      *
-     * Pop all columns.
+     * Pop all columns. Returns the popped head, if any.
      */
-    pub fn pop_col(&mut self) {
-        self.pop_head();
+    pub fn pop_col(&mut self) -> Option<String> {
+        let head = self.heads.pop();
 
         // rows
         self.rows.iter_mut().for_each(|row| {
             row.pop();
         });
+
+        head
     }
 
     /**
@@ -227,8 +318,11 @@ impl CsvFile {
      *
      * Push new row.
      */
-    pub fn push_row(&mut self, value: &[&str]) {
-        self.rows.push(self.row_mapper(value));
+    pub fn push_row(&mut self, value: &[&str]) -> Result<(), CsvError> {
+        let row = self.fit_row(value)?;
+        self.rows.push(row);
+
+        Ok(())
     }
 
     /**
@@ -236,12 +330,12 @@ impl CsvFile {
      *
      * Set row to target position.
      */
-    pub fn set_row(&mut self, position: usize, value: &[&str]) {
-        if let None = self.rows.get(position) {
-            return;
-        }
+    pub fn set_row(&mut self, position: usize, value: &[&str]) -> Result<(), CsvError> {
+        Self::check_index(position, self.rows.len())?;
 
-        self.rows[position] = self.row_mapper(value);
+        self.rows[position] = self.fit_row(value)?;
+
+        Ok(())
     }
 
     /**
@@ -249,30 +343,78 @@ impl CsvFile {
      *
      * Insert new row.
      */
-    pub fn insert_row(&mut self, position: usize, value: &[&str]) {
-        self.rows.insert(position, self.row_mapper(value));
+    pub fn insert_row(&mut self, position: usize, value: &[&str]) -> Result<(), CsvError> {
+        Self::check_index(position, self.rows.len() + 1)?;
+
+        let row = self.fit_row(value)?;
+        self.rows.insert(position, row);
+
+        Ok(())
     }
 
     /**
      * Delete Row
      *
+     * Delete row at target position.
+     */
+    pub fn delete_row(&mut self, position: usize) -> Result<CsvRow, CsvError> {
+        Self::check_index(position, self.rows.len())?;
+
+        Ok(self.rows.remove(position))
+    }
+
+    /**
+     * Pop Row
+     *
+     * Pop last row.
+     */
+    pub fn pop_row(&mut self) -> Option<CsvRow> {
+        self.rows.pop()
+    }
+
+    /**
+     * Check Index
      *
+     * Fail when `index` isn't below `len`.
      */
-    pub fn delete_row(&mut self, position: usize) -> Option<Vec<String>> {
-        if let None = self.rows.get(position) {
-            return None;
+    fn check_index(index: usize, len: usize) -> Result<(), CsvError> {
+        if index >= len {
+            return Err(CsvError::OutOfBounds { index, len });
         }
 
-        Some(self.rows.remove(position))
+        Ok(())
     }
 
     /**
-     * Pop Row
+     * Remove Cells
      *
+     * Remove the cell at `position` from every row that has one.
+     */
+    fn remove_cells(&mut self, position: usize) {
+        for row in self.rows.iter_mut() {
+            if row.get(position).is_none() {
+                continue;
+            }
+
+            row.remove(position);
+        }
+    }
+
+    /**
+     * Check Width
      *
+     * Fail when a row of `width` cells wouldn't fit under the heads.
      */
-    pub fn pop_row(&mut self) -> Option<Vec<String>> {
-        self.rows.pop()
+    fn check_width(&self, width: usize) -> Result<(), CsvError> {
+        if self.shape != ShapePolicy::Flexible && !self.heads.is_empty() && width > self.heads.len()
+        {
+            return Err(CsvError::ShapeMismatch {
+                expected: self.heads.len(),
+                found: width,
+            });
+        }
+
+        Ok(())
     }
 
     /**
@@ -286,87 +428,393 @@ impl CsvFile {
             .map(std::string::ToString::to_string)
             .collect::<Vec<String>>()
     }
+
+    /**
+     * Fit Row
+     *
+     * Maps slices into CsvRow, fitted to the heads by the shape policy.
+     */
+    fn fit_row(&self, value: &[&str]) -> Result<CsvRow, CsvError> {
+        self.shape
+            .fit(self.heads.len(), self.row_mapper(value))
+            .ok_or(CsvError::ShapeMismatch {
+                expected: self.heads.len(),
+                found: value.len(),
+            })
+    }
 }
 
 impl CsvFile {
     /**
      * Read
      *
-     * Read CSV file.
+     * Read CSV file. Every row must have as many fields as the header.
      */
-    pub fn read<P: AsRef<Path>>(path: P) -> Result<CsvFile, io::Error> {
-        let file = File::open(path)?;
-        let buf = BufReader::new(file);
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<CsvFile, CsvError> {
+        Self::read_with(path, &ReadOptions::default())
+    }
 
-        let contents = buf
-            .lines()
-            .flatten()
-            .filter(|line| !line.is_empty())
-            .map(|row| {
-                row.split(CSV_SEP)
-                    .map(std::string::ToString::to_string)
-                    .collect::<Vec<String>>()
-            })
-            .collect::<Vec<CsvRow>>();
+    /**
+     * Read With
+     *
+     * Read CSV file with the given options.
+     */
+    pub fn read_with<P: AsRef<Path>>(path: P, options: &ReadOptions) -> Result<CsvFile, CsvError> {
+        Self::from_reader(File::open(path)?, options)
+    }
 
-        Ok(CsvFile {
-            heads: contents[0].clone(),
-            rows: contents[1..].to_vec(),
-        })
+    /**
+     * From Reader
+     *
+     * Read CSV data from any `Read`, e.g. stdin or an in-memory buffer.
+     */
+    pub fn from_reader<R: Read>(reader: R, options: &ReadOptions) -> Result<CsvFile, CsvError> {
+        let mut reader = CsvReader::with_dialect(reader, options.dialect.clone());
+
+        let mut file = CsvFile::new();
+        file.shape = options.shape.clone();
+
+        let first = match (reader.read_row()?, options.empty) {
+            (Some(first), _) => first,
+            (None, EmptyPolicy::Error) => return Err(CsvError::Empty),
+            (None, EmptyPolicy::Allow) => return Ok(file),
+        };
+
+        if options.has_headers {
+            file.heads = first;
+        } else {
+            file.rows.push(first);
+        }
+
+        while let Some(row) = reader.read_row()? {
+            let found = row.len();
+            let Some(row) = file.shape.fit(file.heads.len(), row) else {
+                let mut position = reader.position();
+                position.field = found.min(file.heads.len());
+
+                return Err(CsvError::RaggedRow {
+                    position,
+                    expected: file.heads.len(),
+                    found,
+                });
+            };
+
+            file.rows.push(row);
+        }
+
+        Ok(file)
     }
 
     /**
      * Write
      *
-     * Write CSV file.
+     * Write CSV file. Cells are quoted only when needed.
+     *
+     * Reading the file back with matching options gives an equal `CsvFile`:
+     * `has_headers` off for a file without heads, the file's shape policy
+     * for ragged rows, and `EmptyPolicy::Allow` for an empty file.
+     */
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), CsvError> {
+        self.write_with(path, &WriteOptions::default())
+    }
+
+    /**
+     * Write With
+     *
+     * Write CSV file with the given options.
      */
-    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<(), io::Error> {
-        let file = File::create(path)?;
-        let mut buf = BufWriter::new(file);
+    pub fn write_with<P: AsRef<Path>>(
+        &self,
+        path: P,
+        options: &WriteOptions,
+    ) -> Result<(), CsvError> {
+        self.to_writer(File::create(path)?, options)
+    }
+
+    /**
+     * To Writer
+     *
+     * Write CSV data to any `Write`, e.g. stdout or a `Vec<u8>`.
+     */
+    pub fn to_writer<W: Write>(&self, writer: W, options: &WriteOptions) -> Result<(), CsvError> {
+        let mut writer = CsvWriter::with_options(writer, options.clone());
 
         if !self.heads.is_empty() {
-            buf.write(self.heads().join(CSV_SEP).as_bytes())?;
-            buf.write("\n".as_bytes())?;
+            writer.write_header(&self.heads)?;
         }
 
-        for row in self.rows().into_iter() {
-            buf.write(row.join(CSV_SEP).as_bytes())?;
-            buf.write("\n".as_bytes())?;
+        for row in self.rows().iter() {
+            writer.write_record(row)?;
         }
 
-        buf.flush()
+        writer.flush()
     }
 }
 
-impl fmt::Display for CsvFile {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        // border
-        for head in self.heads.iter() {
-            write!(f, "- {} -", "-".repeat(head.len()))?;
-        }
-        write!(f, "\n")?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        // heads
-        for head in self.heads.iter() {
-            write!(f, "- {} -", head)?;
+    fn file(heads: &[&str], rows: &[&[&str]]) -> CsvFile {
+        let mut file = CsvFile::new();
+        for head in heads {
+            file.push_head(head);
+        }
+        for row in rows {
+            file.push_row(row).unwrap();
         }
-        write!(f, "\n")?;
+        file
+    }
+
+    fn round_trip(file: &CsvFile, write: &WriteOptions, read: &ReadOptions) -> CsvFile {
+        let mut bytes = Vec::new();
+        file.to_writer(&mut bytes, write).unwrap();
 
-        // border
-        for head in self.heads.iter() {
-            write!(f, "- {} -", "-".repeat(head.len()))?;
+        CsvFile::from_reader(&bytes[..], read).unwrap()
+    }
+
+    #[test]
+    fn read_write_round_trips_files_with_heads() {
+        let file = file(
+            &["name", "note, quoted", "\"q\""],
+            &[
+                &["a,b", "say \"hi\"", "line\nbreak"],
+                &["", " padded ", "crlf\r\nend"],
+                &["1", "", ""],
+            ],
+        );
+
+        for quote_style in [
+            QuoteStyle::Minimal,
+            QuoteStyle::Always,
+            QuoteStyle::NonNumeric,
+        ] {
+            let write = WriteOptions {
+                quote_style,
+                ..WriteOptions::default()
+            };
+            assert_eq!(round_trip(&file, &write, &ReadOptions::default()), file);
         }
-        write!(f, "\n")?;
+    }
 
-        // rows
-        for row in self.rows.iter() {
-            for col in row.iter() {
-                write!(f, "- {} -", col)?;
-            }
+    #[test]
+    fn read_write_round_trips_a_single_empty_cell() {
+        let file = file(&["only"], &[&[""], &["x"], &[""]]);
+
+        assert_eq!(
+            round_trip(&file, &WriteOptions::default(), &ReadOptions::default()),
+            file
+        );
+    }
+
+    #[test]
+    fn read_write_round_trips_files_without_heads() {
+        let file = file(&[], &[&["1", "2"], &["3"], &["a,b", ""]]);
+        let read = ReadOptions {
+            has_headers: false,
+            ..ReadOptions::default()
+        };
 
-            write!(f, "\n")?;
+        assert_eq!(round_trip(&file, &WriteOptions::default(), &read), file);
+    }
+
+    #[test]
+    fn read_write_round_trips_ragged_rows() {
+        let mut file = file(&["a", "b", "c"], &[&["1", "2", "3"]]);
+        file.set_shape_policy(ShapePolicy::Flexible);
+        file.push_row(&["short"]).unwrap();
+        file.push_row(&["1", "2", "3", "long"]).unwrap();
+
+        let mut bytes = Vec::new();
+        file.to_writer(&mut bytes, &WriteOptions::default())
+            .unwrap();
+        let err = CsvFile::from_reader(&bytes[..], &ReadOptions::default()).unwrap_err();
+        assert!(matches!(err, CsvError::RaggedRow { found: 1, .. }));
+
+        let read = ReadOptions {
+            shape: ShapePolicy::Flexible,
+            ..ReadOptions::default()
+        };
+        assert_eq!(round_trip(&file, &WriteOptions::default(), &read), file);
+    }
+
+    #[test]
+    fn skipped_header_reads_back_as_a_row() {
+        let file = file(&["a", "b"], &[&["1", "2"]]);
+        let write = WriteOptions {
+            has_headers: false,
+            ..WriteOptions::default()
+        };
+        let read = ReadOptions {
+            has_headers: false,
+            ..ReadOptions::default()
+        };
+
+        let read = round_trip(&file, &write, &read);
+        assert!(read.heads().is_empty());
+        assert_eq!(read.rows(), file.rows());
+    }
+
+    #[test]
+    fn headless_rows_read_back_as_heads_by_default() {
+        let file = file(&[], &[&["1", "2"], &["3", "4"]]);
+        let read = round_trip(&file, &WriteOptions::default(), &ReadOptions::default());
+
+        assert_eq!(read.heads(), &["1", "2"]);
+        assert_eq!(read.rows(), &[vec!["3", "4"]]);
+    }
+
+    #[test]
+    fn empty_file_reads_back_only_when_allowed() {
+        let mut bytes = Vec::new();
+        CsvFile::new()
+            .to_writer(&mut bytes, &WriteOptions::default())
+            .unwrap();
+        assert!(bytes.is_empty());
+
+        let err = CsvFile::from_reader(&bytes[..], &ReadOptions::default()).unwrap_err();
+        assert!(matches!(err, CsvError::Empty));
+
+        let allow = ReadOptions {
+            empty: EmptyPolicy::Allow,
+            ..ReadOptions::default()
+        };
+        assert_eq!(
+            round_trip(&CsvFile::new(), &WriteOptions::default(), &allow),
+            CsvFile::new()
+        );
+    }
+
+    fn read(input: &str, empty: EmptyPolicy) -> Result<CsvFile, CsvError> {
+        let options = ReadOptions {
+            empty,
+            ..ReadOptions::default()
+        };
+
+        CsvFile::from_reader(input.as_bytes(), &options)
+    }
+
+    #[test]
+    fn empty_input_follows_the_empty_policy() {
+        assert!(matches!(read("", EmptyPolicy::Error), Err(CsvError::Empty)));
+        assert_eq!(read("", EmptyPolicy::Allow).unwrap(), CsvFile::new());
+    }
+
+    #[test]
+    fn blank_lines_only_count_as_empty() {
+        let input = "\n\r\n\n";
+
+        assert!(matches!(
+            read(input, EmptyPolicy::Error),
+            Err(CsvError::Empty)
+        ));
+        assert_eq!(read(input, EmptyPolicy::Allow).unwrap(), CsvFile::new());
+    }
+
+    #[test]
+    fn header_only_input_has_heads_and_no_rows() {
+        for empty in [EmptyPolicy::Error, EmptyPolicy::Allow] {
+            let file = read("a,b\n\n", empty).unwrap();
+
+            assert_eq!(file.heads(), &["a", "b"]);
+            assert!(file.rows().is_empty());
         }
+    }
 
-        write!(f, "")
+    #[test]
+    fn head_changes_keep_strict_rows_in_line() {
+        let mut strict = file(&["a", "b"], &[&["1", "2"]]);
+
+        assert_eq!(strict.delete_head(0).unwrap(), "a");
+        assert_eq!(strict.rows(), &[vec!["2"]]);
+        assert!(matches!(
+            strict.push_row(&["y", "z"]),
+            Err(CsvError::ShapeMismatch {
+                expected: 1,
+                found: 2
+            })
+        ));
+
+        strict.push_head("c");
+        strict.insert_head(0, "first").unwrap();
+        assert_eq!(strict.heads(), &["first", "b", "c"]);
+        assert_eq!(strict.rows(), &[vec!["", "2", ""]]);
+
+        assert_eq!(strict.pop_head().as_deref(), Some("c"));
+        assert_eq!(strict.rows(), &[vec!["", "2"]]);
+    }
+
+    #[test]
+    fn head_changes_pad_with_the_pad_value() {
+        let mut padded = file(&["a"], &[&["1"]]);
+        padded.set_shape_policy(ShapePolicy::Pad("-".into()));
+
+        padded.push_head("b");
+        assert_eq!(padded.rows(), &[vec!["1", "-"]]);
+    }
+
+    #[test]
+    fn head_changes_leave_flexible_rows_alone() {
+        let mut flexible = file(&["a", "b"], &[&["1", "2"]]);
+        flexible.set_shape_policy(ShapePolicy::Flexible);
+
+        flexible.delete_head(0).unwrap();
+        flexible.push_head("c");
+        flexible.pop_head();
+        assert_eq!(flexible.rows(), &[vec!["1", "2"]]);
+    }
+
+    #[test]
+    fn column_changes_remove_cells_once() {
+        let mut strict = file(&["a", "b", "c"], &[&["1", "2", "3"]]);
+
+        strict.delete_col(0).unwrap();
+        assert_eq!(strict.pop_col().as_deref(), Some("c"));
+        assert_eq!(strict.heads(), &["b"]);
+        assert_eq!(strict.rows(), &[vec!["2"]]);
+    }
+
+    #[test]
+    fn equality_ignores_shape_policy() {
+        let strict = file(&["a", "b"], &[&["1", "2"]]);
+        let mut padded = strict.clone();
+        padded.set_shape_policy(ShapePolicy::Pad(String::new()));
+
+        assert_eq!(strict, padded);
+
+        padded.push_row(&["3"]).unwrap();
+        assert_ne!(strict, padded);
+    }
+
+    #[test]
+    fn mutators_reject_out_of_bounds_indices() {
+        let original = file(&["a", "b"], &[&["1", "2"]]);
+        let mut file = original.clone();
+
+        // Each index is one past the last valid one, so it equals the length.
+        let cases: [(&str, Result<(), CsvError>, usize); 12] = [
+            ("set_head", file.set_head(2, "x"), 2),
+            ("insert_head", file.insert_head(3, "x"), 3),
+            ("delete_head", file.delete_head(2).map(drop), 2),
+            ("push_col", file.push_col(1, "x"), 1),
+            ("set_col row", file.set_col(1, 0, "x"), 1),
+            ("set_col col", file.set_col(0, 2, "x"), 2),
+            ("insert_col row", file.insert_col(1, 0, "x"), 1),
+            ("insert_col col", file.insert_col(0, 3, "x"), 3),
+            ("delete_col", file.delete_col(2), 2),
+            ("set_row", file.set_row(1, &["x", "y"]), 1),
+            ("insert_row", file.insert_row(2, &["x", "y"]), 2),
+            ("delete_row", file.delete_row(1).map(drop), 1),
+        ];
+
+        for (name, result, index) in cases {
+            match result {
+                Err(CsvError::OutOfBounds { index: i, len }) => {
+                    assert_eq!((i, len), (index, index), "{name}")
+                }
+                other => panic!("{name}: expected OutOfBounds, got {other:?}"),
+            }
+        }
+        assert_eq!(file, original);
     }
 }
@@ -0,0 +1,378 @@
+use crate::display::display_width;
+use crate::{CsvError, CsvFile, CsvRow};
+
+/**
+ * Align
+ *
+ * Alignment of a Markdown table column.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Align {
+    /// No alignment, `---`.
+    #[default]
+    None,
+    /// `:---`
+    Left,
+    /// `:---:`
+    Center,
+    /// `---:`
+    Right,
+}
+
+/**
+ * Markdown Options
+ *
+ * Options for writing Markdown tables.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarkdownOptions {
+    /// Alignment of each column, by position. Columns past the end have none.
+    pub align: Vec<Align>,
+}
+
+impl CsvFile {
+    /**
+     * To Markdown
+     *
+     * GitHub-flavored pipe table, with the heads as the header row.
+     */
+    pub fn to_markdown(&self) -> String {
+        self.to_markdown_with(&MarkdownOptions::default())
+    }
+
+    /**
+     * To Markdown With
+     *
+     * GitHub-flavored pipe table with the given options. `|`, `\\`, `<` and
+     * `&` are escaped with a backslash, line breaks are written as `<br>` and
+     * leading or trailing whitespace as character references, e.g. `&#32;`,
+     * so `from_markdown` reads every cell back as it was, except that `\r\n`
+     * and `\r` come back as `\n`. Cells are padded so the source lines up.
+     */
+    pub fn to_markdown_with(&self, options: &MarkdownOptions) -> String {
+        let columns = self
+            .rows
+            .iter()
+            .map(Vec::len)
+            .chain([self.heads.len()])
+            .max()
+            .unwrap_or_default();
+
+        if columns == 0 {
+            return String::new();
+        }
+
+        let escape = |cells: &[String]| -> Vec<String> {
+            (0..columns)
+                .map(|index| escape_cell(cells.get(index).map_or("", String::as_str)))
+                .collect()
+        };
+        let heads = escape(&self.heads);
+        let rows = self.rows.iter().map(|row| escape(row)).collect::<Vec<_>>();
+
+        let align = (0..columns)
+            .map(|index| options.align.get(index).copied().unwrap_or_default())
+            .collect::<Vec<_>>();
+
+        // Delimiter cells need at least three dashes.
+        let widths = (0..columns)
+            .map(|index| {
+                rows.iter()
+                    .chain([&heads])
+                    .map(|row| display_width(&row[index]))
+                    .max()
+                    .unwrap_or_default()
+                    .max(3)
+            })
+            .collect::<Vec<usize>>();
+
+        let mut markdown = String::new();
+
+        push_line(&mut markdown, &heads, &widths, &align);
+
+        let delimiters = widths
+            .iter()
+            .zip(&align)
+            .map(|(width, align)| match align {
+                Align::None => "-".repeat(*width),
+                Align::Left => format!(":{}", "-".repeat(width - 1)),
+                Align::Center => format!(":{}:", "-".repeat(width - 2)),
+                Align::Right => format!("{}:", "-".repeat(width - 1)),
+            })
+            .collect::<Vec<_>>();
+        push_line(&mut markdown, &delimiters, &widths, &align);
+
+        for row in &rows {
+            push_line(&mut markdown, row, &widths, &align);
+        }
+
+        markdown
+    }
+
+    /**
+     * From Markdown
+     *
+     * Read the first pipe table in `markdown`. Its header row becomes the
+     * heads. Cells are trimmed; backslash escapes of punctuation, `<br>` and
+     * numeric character references such as `&#32;` are decoded. As in GitHub
+     * rendering, short rows are padded with empty cells and extra cells are
+     * dropped. The table ends at the first blank line. Fails with
+     * `InvalidMarkdown` when there is no table.
+     */
+    pub fn from_markdown(markdown: &str) -> Result<CsvFile, CsvError> {
+        let lines = markdown.lines().collect::<Vec<_>>();
+
+        let start = lines
+            .windows(2)
+            .position(|pair| {
+                let heads = split_line(pair[0]);
+                let delimiters = split_line(pair[1]);
+
+                pair[0].contains('|')
+                    && pair[1].contains('|')
+                    && heads.len() == delimiters.len()
+                    && delimiters.iter().all(|cell| is_delimiter(cell))
+            })
+            .ok_or_else(|| CsvError::InvalidMarkdown {
+                message: "no pipe table found".to_string(),
+            })?;
+
+        let heads = split_line(lines[start]);
+        let rows = lines[start + 2..]
+            .iter()
+            .take_while(|line| !line.trim().is_empty())
+            .map(|line| {
+                let mut row = split_line(line);
+                row.resize(heads.len(), String::new());
+                row
+            })
+            .collect::<Vec<CsvRow>>();
+
+        Ok(CsvFile {
+            heads,
+            rows,
+            ..CsvFile::default()
+        })
+    }
+}
+
+/**
+ * Push Line
+ *
+ * Push one table line of padded cells.
+ */
+fn push_line(markdown: &mut String, cells: &[String], widths: &[usize], align: &[Align]) {
+    markdown.push('|');
+
+    for ((cell, width), align) in cells.iter().zip(widths).zip(align) {
+        let pad = width - display_width(cell);
+        let left = match align {
+            Align::Right => pad,
+            Align::Center => pad / 2,
+            Align::None | Align::Left => 0,
+        };
+
+        markdown.push(' ');
+        markdown.push_str(&" ".repeat(left));
+        markdown.push_str(cell);
+        markdown.push_str(&" ".repeat(pad - left));
+        markdown.push_str(" |");
+    }
+
+    markdown.push('\n');
+}
+
+/**
+ * Escape Cell
+ *
+ * Cell text that is safe inside a pipe table and survives `split_line`.
+ */
+fn escape_cell(cell: &str) -> String {
+    let cell = cell.replace("\r\n", "\n");
+    // Whitespace outside this range would be trimmed when read back.
+    let start = cell.len() - cell.trim_start().len();
+    let end = cell.trim_end().len();
+
+    let mut escaped = String::with_capacity(cell.len());
+    for (index, c) in cell.char_indices() {
+        match c {
+            '\n' | '\r' => escaped.push_str("<br>"),
+            '\\' | '|' | '<' | '&' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            c if c.is_whitespace() && (index < start || index >= end) => {
+                escaped.push_str(&format!("&#{};", c as u32));
+            }
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/**
+ * Split Line
+ *
+ * Cells of a table line, without the outer pipes, trimmed and unescaped.
+ */
+fn split_line(line: &str) -> Vec<String> {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            // Escapes stay in the cell until it is unescaped.
+            '\\' => {
+                cell.push(c);
+                cell.extend(chars.next());
+            }
+            '|' => cells.push(std::mem::take(&mut cell)),
+            _ => cell.push(c),
+        }
+    }
+
+    // A trailing pipe closes the last cell rather than starting a new one.
+    if !cell.trim().is_empty() || cells.is_empty() {
+        cells.push(cell);
+    }
+
+    cells
+        .iter()
+        .map(|cell| unescape_cell(cell.trim()))
+        .collect()
+}
+
+/**
+ * Unescape Cell
+ *
+ * Decode backslash escapes, line breaks and numeric character references.
+ */
+fn unescape_cell(cell: &str) -> String {
+    let mut unescaped = String::with_capacity(cell.len());
+    let mut rest = cell;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some(escaped) = rest
+            .strip_prefix('\\')
+            .and_then(|after| after.chars().next())
+            .filter(char::is_ascii_punctuation)
+        {
+            unescaped.push(escaped);
+            rest = &rest[2..];
+        } else if let Some(after) = ["<br>", "<br/>", "<br />"]
+            .iter()
+            .find_map(|br| rest.strip_prefix(br))
+        {
+            unescaped.push('\n');
+            rest = after;
+        } else if let Some((reference, after)) = char_reference(rest) {
+            unescaped.push(reference);
+            rest = after;
+        } else {
+            unescaped.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    unescaped
+}
+
+/**
+ * Char Reference
+ *
+ * Character of a leading numeric reference, e.g. `&#32;` or `&#x20;`, and
+ * the text after it.
+ */
+fn char_reference(text: &str) -> Option<(char, &str)> {
+    let (number, after) = text.strip_prefix("&#")?.split_once(';')?;
+
+    let code = match number.strip_prefix(['x', 'X']) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+        None => number.parse().ok()?,
+    };
+
+    char::from_u32(code).map(|c| (c, after))
+}
+
+/**
+ * Is Delimiter
+ *
+ * Whether a cell of the delimiter row is valid, e.g. `---`, `:--` or `:-:`.
+ */
+fn is_delimiter(cell: &str) -> bool {
+    let dashes = cell.strip_prefix(':').unwrap_or(cell);
+    let dashes = dashes.strip_suffix(':').unwrap_or(dashes);
+
+    !dashes.is_empty() && dashes.chars().all(|c| c == '-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(heads: &[&str], rows: &[&[&str]]) -> CsvFile {
+        let mut file = CsvFile::new();
+        for head in heads {
+            file.push_head(head);
+        }
+        for row in rows {
+            file.push_row(row).unwrap();
+        }
+        file
+    }
+
+    #[test]
+    fn writes_a_padded_table() {
+        let file = file(&["name", "n"], &[&["Ann", "1"]]);
+        let options = MarkdownOptions {
+            align: vec![Align::Left, Align::Right],
+        };
+
+        assert_eq!(
+            file.to_markdown_with(&options),
+            "| name |   n |\n| :--- | --: |\n| Ann  |   1 |\n"
+        );
+    }
+
+    #[test]
+    fn round_trips_special_cells() {
+        let file = file(
+            &["a|b", " padded "],
+            &[
+                &["x | y", "line\nbreak"],
+                &["<br> stays", "C:\\dir\\"],
+                &["&#32; &amp;", "\n"],
+                &["  ", "\\|"],
+            ],
+        );
+
+        let markdown = file.to_markdown();
+        assert_eq!(
+            CsvFile::from_markdown(&markdown).unwrap(),
+            file,
+            "{markdown}"
+        );
+    }
+
+    #[test]
+    fn reads_hand_written_tables() {
+        let markdown = "Intro\n\n a | b\n---|:-:\n1 | x<br/>y | extra\n2\n\nafter | table\n";
+        let file = CsvFile::from_markdown(markdown).unwrap();
+
+        assert_eq!(file.heads(), &["a", "b"]);
+        assert_eq!(file.rows(), &[vec!["1", "x\ny"], vec!["2", ""]]);
+    }
+
+    #[test]
+    fn missing_table_is_invalid() {
+        assert!(matches!(
+            CsvFile::from_markdown("no | table\nhere"),
+            Err(CsvError::InvalidMarkdown { .. })
+        ));
+    }
+}
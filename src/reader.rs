@@ -0,0 +1,610 @@
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use crate::{
+    ByteRecord, CsvError, CsvRow, Dialect, Position, ShapePolicy, StringRecord, Terminator,
+};
+
+const BUF_SIZE: usize = 8 * 1024;
+
+/**
+ * Empty Policy
+ *
+ * What reading does with input that has no records.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmptyPolicy {
+    /// Fail with `CsvError::Empty`.
+    #[default]
+    Error,
+    /// Return a `CsvFile` with no heads and no rows.
+    Allow,
+}
+
+/**
+ * Read Options
+ *
+ * Options for reading CSV files.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadOptions {
+    pub dialect: Dialect,
+    pub empty: EmptyPolicy,
+    /// Policy for rows that don't match the header, kept by the read file.
+    pub shape: ShapePolicy,
+    /// Read the first record as the heads. Otherwise every record is a row.
+    pub has_headers: bool,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self {
+            dialect: Dialect::default(),
+            empty: EmptyPolicy::default(),
+            shape: ShapePolicy::default(),
+            has_headers: true,
+        }
+    }
+}
+
+/**
+ * State
+ *
+ * Parser state while walking a record.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    /// At the start of a field.
+    FieldStart,
+    /// Inside an unquoted field.
+    Unquoted,
+    /// Inside a quoted field.
+    Quoted,
+    /// Just saw a quote inside a quoted field.
+    QuoteInQuoted,
+}
+
+/**
+ * Source
+ *
+ * Buffered input that can look ahead a few bytes.
+ */
+#[derive(Debug)]
+struct Source<R> {
+    inner: R,
+    buf: Vec<u8>,
+    pos: usize,
+    len: usize,
+    eof: bool,
+}
+
+impl<R: Read> Source<R> {
+    fn new(inner: R) -> Self {
+        Self {
+            inner,
+            buf: vec![0; BUF_SIZE],
+            pos: 0,
+            len: 0,
+            eof: false,
+        }
+    }
+
+    /**
+     * Fill
+     *
+     * Unread bytes, with at least `need` of them unless the input ended.
+     */
+    fn fill(&mut self, need: usize) -> io::Result<&[u8]> {
+        while self.len - self.pos < need && !self.eof {
+            if self.pos > 0 {
+                self.buf.copy_within(self.pos..self.len, 0);
+                self.len -= self.pos;
+                self.pos = 0;
+            }
+
+            match self.inner.read(&mut self.buf[self.len..]) {
+                Ok(0) => self.eof = true,
+                Ok(read) => self.len += read,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+
+        Ok(&self.buf[self.pos..self.len])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.pos += amount;
+    }
+}
+
+/**
+ * CSV Reader
+ *
+ * Streaming CSV reader over any `Read`. Records are parsed one at a time,
+ * so memory use doesn't grow with the input.
+ */
+#[derive(Debug)]
+pub struct CsvReader<R> {
+    source: Source<R>,
+    delimiter: Vec<u8>,
+    quote: Vec<u8>,
+    escape: Vec<u8>,
+    terminator: Terminator,
+    trim: bool,
+    /// Reused record for `read_row`.
+    record: StringRecord,
+    /// Where each field of the last record started.
+    starts: Vec<Position>,
+    /// Where the last record started.
+    position: Position,
+    byte: u64,
+    line: u64,
+    done: bool,
+}
+
+impl CsvReader<File> {
+    /**
+     * From Path
+     *
+     * Open a CSV file for reading.
+     */
+    pub fn from_path<P: AsRef<Path>>(path: P, dialect: Dialect) -> Result<Self, CsvError> {
+        Ok(Self::with_dialect(File::open(path)?, dialect))
+    }
+}
+
+impl<R: Read> CsvReader<R> {
+    /**
+     * New
+     *
+     * New reader with the default dialect.
+     */
+    pub fn new(reader: R) -> Self {
+        Self::with_dialect(reader, Dialect::default())
+    }
+
+    /**
+     * With Dialect
+     *
+     * New reader with the given dialect.
+     */
+    pub fn with_dialect(reader: R, dialect: Dialect) -> Self {
+        Self {
+            source: Source::new(reader),
+            delimiter: dialect.delimiter.clone().into_bytes(),
+            quote: dialect.quote_bytes(),
+            escape: dialect.escape_bytes(),
+            terminator: dialect.terminator,
+            trim: dialect.trim,
+            record: StringRecord::new(),
+            starts: Vec::new(),
+            position: Position {
+                byte: 0,
+                line: 1,
+                field: 0,
+            },
+            byte: 0,
+            line: 1,
+            done: false,
+        }
+    }
+
+    /**
+     * Position
+     *
+     * Where the last record read started.
+     */
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /**
+     * Read Row
+     *
+     * Read the next record into a new row, or `None` at the end of the input.
+     */
+    pub fn read_row(&mut self) -> Result<Option<CsvRow>, CsvError> {
+        let mut record = std::mem::take(&mut self.record);
+        let result = self.read_record(&mut record);
+        let row = record.to_row();
+        self.record = record;
+
+        Ok(result?.then_some(row))
+    }
+
+    /**
+     * Read Record
+     *
+     * Read the next record into `record`, reusing its buffers. Returns
+     * `false` at the end of the input.
+     */
+    pub fn read_record(&mut self, record: &mut StringRecord) -> Result<bool, CsvError> {
+        let bytes = record.byte_record_mut();
+        if !self.read_byte_record(bytes)? {
+            return Ok(false);
+        }
+
+        if let Err(index) = bytes.validate() {
+            // Leave no invalid text behind in the record.
+            bytes.clear();
+            return Err(CsvError::InvalidUtf8 {
+                position: self.starts[index],
+            });
+        }
+
+        Ok(true)
+    }
+
+    /**
+     * Read Byte Record
+     *
+     * Read the next record into `record` without UTF-8 validation, reusing
+     * its buffers. Returns `false` at the end of the input.
+     */
+    pub fn read_byte_record(&mut self, record: &mut ByteRecord) -> Result<bool, CsvError> {
+        record.clear();
+        self.starts.clear();
+
+        if self.done {
+            return Ok(false);
+        }
+
+        let result = self.parse_record(record);
+        if !matches!(result, Ok(true)) {
+            self.done = true;
+        }
+
+        result
+    }
+
+    /**
+     * Parse Record
+     *
+     * Parse the next record. Quoted fields may contain delimiters, line
+     * breaks and escaped quotes. Blank lines are skipped.
+     */
+    fn parse_record(&mut self, record: &mut ByteRecord) -> Result<bool, CsvError> {
+        let lookahead = self
+            .delimiter
+            .len()
+            .max(self.quote.len())
+            .max(self.escape.len() + 5);
+        let mut state = State::FieldStart;
+        // Bytes of the current field that trimming must keep, i.e. quoted or escaped text.
+        let mut kept = 0;
+
+        loop {
+            if state == State::FieldStart {
+                let start = Position {
+                    byte: self.byte,
+                    line: self.line,
+                    field: record.len(),
+                };
+                self.starts.truncate(record.len());
+                self.starts.push(start);
+                if record.is_empty() {
+                    self.position = start;
+                }
+            }
+
+            let rest = self.source.fill(lookahead)?;
+            let Some(&byte) = rest.first() else {
+                break;
+            };
+            let at = |token: &[u8]| !token.is_empty() && rest.starts_with(token);
+            let mut ended = false;
+
+            let step = if state == State::Quoted {
+                if at(&self.escape) && rest.len() > self.escape.len() {
+                    let len = self.escape.len() + char_len(&rest[self.escape.len()..]);
+                    record.extend(&rest[self.escape.len()..len]);
+                    kept = record.pending();
+                    len
+                } else if at(&self.quote) {
+                    kept = record.pending();
+                    state = State::QuoteInQuoted;
+                    self.quote.len()
+                } else {
+                    record.push_byte(byte);
+                    1
+                }
+            } else if state == State::QuoteInQuoted && self.escape.is_empty() && at(&self.quote) {
+                record.extend(&self.quote);
+                kept = record.pending();
+                state = State::Quoted;
+                self.quote.len()
+            } else if at(&self.delimiter) {
+                record.end_field(self.trim, kept);
+                kept = 0;
+                state = State::FieldStart;
+                self.delimiter.len()
+            } else if let Some(len) = terminator_len(rest, self.terminator) {
+                // Blank lines hold no record.
+                if state != State::FieldStart || !record.is_empty() {
+                    record.end_field(self.trim, kept);
+                    ended = true;
+                }
+                state = State::FieldStart;
+                len
+            } else if state == State::FieldStart && at(&self.quote) {
+                state = State::Quoted;
+                self.quote.len()
+            } else if self.trim && matches!(byte, b' ' | b'\t') && state != State::Unquoted {
+                1
+            } else if at(&self.escape) && rest.len() > self.escape.len() {
+                let len = self.escape.len() + char_len(&rest[self.escape.len()..]);
+                record.extend(&rest[self.escape.len()..len]);
+                kept = record.pending();
+                state = State::Unquoted;
+                len
+            } else {
+                // Lenient: text after a closing quote is kept as-is.
+                record.push_byte(byte);
+                state = State::Unquoted;
+                1
+            };
+
+            for (offset, byte) in rest[..step].iter().enumerate() {
+                if *byte == b'\n' || (*byte == b'\r' && rest.get(offset + 1) != Some(&b'\n')) {
+                    self.line += 1;
+                }
+            }
+            self.byte += step as u64;
+            self.source.consume(step);
+
+            if ended {
+                return Ok(true);
+            }
+        }
+
+        match state {
+            State::Quoted => Err(CsvError::UnterminatedQuote {
+                position: self.starts[record.len()],
+            }),
+            State::FieldStart if record.is_empty() => Ok(false),
+            _ => {
+                record.end_field(self.trim, kept);
+                Ok(true)
+            }
+        }
+    }
+}
+
+impl<R: Read> Iterator for CsvReader<R> {
+    type Item = Result<CsvRow, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_row().transpose()
+    }
+}
+
+/**
+ * Terminator Len
+ *
+ * Length of the line terminator at the start of `rest`, if any.
+ */
+fn terminator_len(rest: &[u8], terminator: Terminator) -> Option<usize> {
+    match (terminator, rest) {
+        (Terminator::Any | Terminator::CrLf, [b'\r', b'\n', ..]) => Some(2),
+        (Terminator::Any | Terminator::Lf, [b'\n', ..]) => Some(1),
+        (Terminator::Any | Terminator::Cr, [b'\r', ..]) => Some(1),
+        _ => None,
+    }
+}
+
+/**
+ * Char Len
+ *
+ * Length of the UTF-8 char at the start of `bytes`.
+ */
+fn char_len(bytes: &[u8]) -> usize {
+    let len = match bytes[0] {
+        0xC0..=0xDF => 2,
+        0xE0..=0xEF => 3,
+        0xF0..=0xF7 => 4,
+        _ => 1,
+    };
+
+    len.min(bytes.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{CsvFile, CsvWriter, Escape, WriteOptions};
+
+    fn rows(input: &str, dialect: Dialect) -> Vec<CsvRow> {
+        CsvReader::with_dialect(input.as_bytes(), dialect)
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn quoted_fields_keep_separators_quotes_and_line_breaks() {
+        let input = "a,\"b,c\",\"say \"\"hi\"\"\"\r\n\"multi\nline\",\"\",end\n";
+
+        assert_eq!(
+            rows(input, Dialect::default()),
+            [
+                vec!["a", "b,c", "say \"hi\""],
+                vec!["multi\nline", "", "end"],
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_blank_lines_and_reads_a_last_line_without_terminator() {
+        assert_eq!(
+            rows("a,b\n\n\r\n1,2", Dialect::default()),
+            [vec!["a", "b"], vec!["1", "2"]]
+        );
+    }
+
+    #[test]
+    fn multi_byte_delimiters_split_fields() {
+        let dialect = Dialect::new("::");
+
+        assert_eq!(
+            rows("a::\"b::c\"::d:e\n", dialect),
+            [vec!["a", "b::c", "d:e"]]
+        );
+    }
+
+    #[test]
+    fn backslash_escapes_inside_and_outside_quotes() {
+        let dialect = Dialect {
+            escape: Escape::Char('\\'),
+            ..Dialect::default()
+        };
+
+        assert_eq!(
+            rows("\"say \\\"hi\\\"\",a\\,b,back\\\\slash\n", dialect),
+            [vec!["say \"hi\"", "a,b", "back\\slash"]]
+        );
+    }
+
+    #[test]
+    fn trim_keeps_quoted_spaces() {
+        let dialect = Dialect {
+            trim: true,
+            ..Dialect::default()
+        };
+
+        assert_eq!(
+            rows(" a ,\t\" b \" , c\t\n", dialect),
+            [vec!["a", " b ", "c"]]
+        );
+    }
+
+    #[test]
+    fn dialects_round_trip_through_the_writer() {
+        let records = [
+            vec!["a", "b::c", "say \"hi\""],
+            vec!["back\\slash", " padded ", "line\nbreak"],
+            vec!["", "x;y", "tab\there"],
+        ];
+        let dialects = [
+            Dialect::default(),
+            Dialect::tsv(),
+            Dialect::semicolon(),
+            Dialect::new("::"),
+            Dialect {
+                escape: Escape::Char('\\'),
+                ..Dialect::default()
+            },
+            Dialect {
+                trim: true,
+                terminator: Terminator::CrLf,
+                ..Dialect::pipe()
+            },
+            Dialect {
+                quote: None,
+                escape: Escape::Char('\\'),
+                ..Dialect::default()
+            },
+        ];
+
+        for dialect in dialects {
+            let options = WriteOptions {
+                dialect: dialect.clone(),
+                ..WriteOptions::default()
+            };
+            let mut writer = CsvWriter::with_options(Vec::new(), options);
+            for record in &records {
+                writer.write_record(record).unwrap();
+            }
+            let bytes = writer.into_inner().unwrap();
+            let text = String::from_utf8(bytes).unwrap();
+
+            assert_eq!(rows(&text, dialect.clone()), records, "{dialect:?}");
+        }
+    }
+
+    #[test]
+    fn unterminated_quote_reports_where_the_field_started() {
+        let mut reader = CsvReader::new("a,b\n1,\"open\nstill open".as_bytes());
+
+        assert_eq!(
+            reader.read_row().unwrap(),
+            Some(vec!["a".into(), "b".into()])
+        );
+        let err = reader.read_row().unwrap_err();
+        assert!(matches!(
+            err,
+            CsvError::UnterminatedQuote {
+                position: Position {
+                    byte: 6,
+                    line: 2,
+                    field: 1
+                }
+            }
+        ));
+        assert!(reader.read_row().unwrap().is_none());
+    }
+
+    #[test]
+    fn ragged_row_reports_its_line() {
+        let err = CsvFile::from_reader(
+            "a,b\n1,2\n\"x\ny\",2,3\n".as_bytes(),
+            &ReadOptions::default(),
+        )
+        .unwrap_err();
+
+        assert!(matches!(
+            err,
+            CsvError::RaggedRow {
+                position: Position { line: 3, .. },
+                expected: 2,
+                found: 3
+            }
+        ));
+    }
+
+    #[test]
+    fn invalid_utf8_clears_the_record() {
+        let mut reader = CsvReader::new(&b"a,\xff\xfe,c\n"[..]);
+        let mut record = StringRecord::new();
+
+        let err = reader.read_record(&mut record).unwrap_err();
+        assert!(matches!(
+            err,
+            CsvError::InvalidUtf8 {
+                position: Position {
+                    byte: 2,
+                    line: 1,
+                    field: 1
+                }
+            }
+        ));
+        assert!(record.is_empty());
+        assert_eq!(record.get(1), None);
+    }
+
+    #[test]
+    fn records_straddling_the_buffer_boundary() {
+        // Shift quotes, a multi-byte character, a CRLF and a multi-byte
+        // delimiter across the end of the first buffer.
+        for pad in BUF_SIZE - 12..=BUF_SIZE + 2 {
+            let first = "x".repeat(pad);
+            let input = format!("{first},\"a\"\"b,\u{e9}\",end\r\n2,3,4\n");
+
+            assert_eq!(
+                rows(&input, Dialect::default()),
+                [
+                    vec![first.as_str(), "a\"b,\u{e9}", "end"],
+                    vec!["2", "3", "4"]
+                ],
+                "pad {pad}"
+            );
+
+            let input = format!("{first}::b::c\n");
+            assert_eq!(
+                rows(&input, Dialect::new("::")),
+                [vec![first.as_str(), "b", "c"]],
+                "pad {pad}"
+            );
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use std::fmt;
+use std::ops::Index;
+use std::str;
+
+use crate::CsvRow;
+
+/**
+ * Byte Record
+ *
+ * Fields of one record stored in a single buffer plus end offsets, so a
+ * record can be refilled without allocating per field.
+ */
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct ByteRecord {
+    bytes: Vec<u8>,
+    ends: Vec<usize>,
+}
+
+impl ByteRecord {
+    /**
+     * New
+     *
+     * Empty record.
+     */
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * Len
+     *
+     * Number of fields.
+     */
+    pub fn len(&self) -> usize {
+        self.ends.len()
+    }
+
+    /**
+     * Is Empty
+     *
+     * Whether the record has no fields.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.ends.is_empty()
+    }
+
+    /**
+     * Get
+     *
+     * Get the field at `index`.
+     */
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        let end = *self.ends.get(index)?;
+        let start = match index {
+            0 => 0,
+            _ => self.ends[index - 1],
+        };
+
+        Some(&self.bytes[start..end])
+    }
+
+    /**
+     * Iter
+     *
+     * Iterate over the fields.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = &[u8]> + '_ {
+        (0..self.len()).map(|index| &self[index])
+    }
+
+    /**
+     * Push Field
+     *
+     * Append a field.
+     */
+    pub fn push_field(&mut self, field: &[u8]) {
+        self.bytes.extend_from_slice(field);
+        self.ends.push(self.bytes.len());
+    }
+
+    /**
+     * Clear
+     *
+     * Remove every field, keeping the allocated buffers.
+     */
+    pub fn clear(&mut self) {
+        self.bytes.clear();
+        self.ends.clear();
+    }
+
+    /**
+     * As Bytes
+     *
+     * Every field's bytes back to back.
+     */
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /**
+     * Push Byte
+     *
+     * Append a byte to the field being built.
+     */
+    pub(crate) fn push_byte(&mut self, byte: u8) {
+        self.bytes.push(byte);
+    }
+
+    /**
+     * Extend
+     *
+     * Append bytes to the field being built.
+     */
+    pub(crate) fn extend(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+    }
+
+    /**
+     * Pending
+     *
+     * Length of the field being built.
+     */
+    pub(crate) fn pending(&self) -> usize {
+        self.bytes.len() - self.ends.last().copied().unwrap_or(0)
+    }
+
+    /**
+     * End Field
+     *
+     * Close the field being built, trimming trailing spaces and tabs past the
+     * first `keep` bytes when asked.
+     */
+    pub(crate) fn end_field(&mut self, trim: bool, keep: usize) {
+        if trim {
+            let start = self.ends.last().copied().unwrap_or(0) + keep;
+            while self.bytes.len() > start && matches!(self.bytes.last(), Some(b' ' | b'\t')) {
+                self.bytes.pop();
+            }
+        }
+
+        self.ends.push(self.bytes.len());
+    }
+
+    /**
+     * Validate
+     *
+     * Check every field is valid UTF-8, returning the first invalid index.
+     */
+    pub(crate) fn validate(&self) -> Result<(), usize> {
+        let text = str::from_utf8(&self.bytes)
+            .map_err(|err| self.ends.partition_point(|&end| end <= err.valid_up_to()))?;
+
+        // Fields must also be split on char boundaries.
+        match self
+            .ends
+            .iter()
+            .position(|&end| !text.is_char_boundary(end))
+        {
+            Some(index) => Err(index),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Index<usize> for ByteRecord {
+    type Output = [u8];
+
+    fn index(&self, index: usize) -> &[u8] {
+        self.get(index).expect("field index out of bounds")
+    }
+}
+
+impl fmt::Debug for ByteRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list()
+            .entries(self.iter().map(String::from_utf8_lossy))
+            .finish()
+    }
+}
+
+/**
+ * String Record
+ *
+ * A `ByteRecord` whose fields are valid UTF-8.
+ */
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct StringRecord(ByteRecord);
+
+impl StringRecord {
+    /**
+     * New
+     *
+     * Empty record.
+     */
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /**
+     * From Byte Record
+     *
+     * Validate a byte record as UTF-8. On failure, returns the index of the
+     * first invalid field.
+     */
+    pub fn from_byte_record(record: ByteRecord) -> Result<Self, usize> {
+        record.validate()?;
+
+        Ok(Self(record))
+    }
+
+    /**
+     * Len
+     *
+     * Number of fields.
+     */
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /**
+     * Is Empty
+     *
+     * Whether the record has no fields.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /**
+     * Get
+     *
+     * Get the field at `index`.
+     */
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.0.get(index).map(|field| {
+            str::from_utf8(field).expect("fields are valid UTF-8 split on char boundaries")
+        })
+    }
+
+    /**
+     * Iter
+     *
+     * Iterate over the fields.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = &str> + '_ {
+        (0..self.len()).map(|index| &self[index])
+    }
+
+    /**
+     * Push Field
+     *
+     * Append a field.
+     */
+    pub fn push_field(&mut self, field: &str) {
+        self.0.push_field(field.as_bytes());
+    }
+
+    /**
+     * Clear
+     *
+     * Remove every field, keeping the allocated buffers.
+     */
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /**
+     * As Byte Record
+     *
+     * Borrow the underlying byte record.
+     */
+    pub fn as_byte_record(&self) -> &ByteRecord {
+        &self.0
+    }
+
+    /**
+     * Into Byte Record
+     *
+     * Take the underlying byte record.
+     */
+    pub fn into_byte_record(self) -> ByteRecord {
+        self.0
+    }
+
+    /**
+     * To Row
+     *
+     * Copy the fields into an owned row.
+     */
+    pub fn to_row(&self) -> Vec<String> {
+        self.iter().map(str::to_string).collect()
+    }
+
+    /**
+     * Byte Record Mut
+     *
+     * Mutable access for the reader, which validates after filling.
+     */
+    pub(crate) fn byte_record_mut(&mut self) -> &mut ByteRecord {
+        &mut self.0
+    }
+}
+
+impl Index<usize> for StringRecord {
+    type Output = str;
+
+    fn index(&self, index: usize) -> &str {
+        self.get(index).expect("field index out of bounds")
+    }
+}
+
+impl fmt::Debug for StringRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl From<StringRecord> for CsvRow {
+    fn from(record: StringRecord) -> Self {
+        record.to_row()
+    }
+}
+
+impl<S: AsRef<str>> FromIterator<S> for StringRecord {
+    fn from_iter<I: IntoIterator<Item = S>>(iter: I) -> Self {
+        let mut record = Self::new();
+        for field in iter {
+            record.push_field(field.as_ref());
+        }
+        record
+    }
+}
+
+impl<'a> FromIterator<&'a [u8]> for ByteRecord {
+    fn from_iter<I: IntoIterator<Item = &'a [u8]>>(iter: I) -> Self {
+        let mut record = Self::new();
+        for field in iter {
+            record.push_field(field);
+        }
+        record
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::iter::Enumerate;
+use std::ops::Index;
+use std::slice;
+use std::str::FromStr;
+
+use crate::{CsvFile, CsvRow};
+
+/**
+ * Row Ref
+ *
+ * Borrowed row that knows the heads, so cells can be read by column name.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowRef<'a> {
+    heads: &'a [String],
+    cells: &'a [String],
+    index: usize,
+}
+
+impl<'a> RowRef<'a> {
+    pub(crate) fn new(heads: &'a [String], cells: &'a [String], index: usize) -> Self {
+        Self {
+            heads,
+            cells,
+            index,
+        }
+    }
+
+    /**
+     * Index
+     *
+     * Position of the row in the file.
+     */
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /**
+     * Len
+     *
+     * Number of cells.
+     */
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /**
+     * Is Empty
+     *
+     * Whether the row has no cells.
+     */
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /**
+     * Heads
+     *
+     * Heads of the file the row belongs to.
+     */
+    pub fn heads(&self) -> &'a [String] {
+        self.heads
+    }
+
+    /**
+     * Cells
+     *
+     * Raw cells of the row.
+     */
+    pub fn cells(&self) -> &'a [String] {
+        self.cells
+    }
+
+    /**
+     * Get
+     *
+     * Get the cell under the head `name`.
+     */
+    pub fn get(&self, name: &str) -> Option<&'a str> {
+        let position = self.heads.iter().position(|head| head == name)?;
+
+        self.get_at(position)
+    }
+
+    /**
+     * Get At
+     *
+     * Get the cell at `position`.
+     */
+    pub fn get_at(&self, position: usize) -> Option<&'a str> {
+        self.cells.get(position).map(String::as_str)
+    }
+
+    /**
+     * Get Parsed
+     *
+     * Parse the cell under the head `name`, e.g. `row.get_parsed::<u32>("Age")`.
+     */
+    pub fn get_parsed<T: FromStr>(&self, name: &str) -> Option<Result<T, T::Err>> {
+        self.get(name).map(str::parse)
+    }
+
+    /**
+     * Iter
+     *
+     * Iterate over the cells.
+     */
+    pub fn iter(&self) -> impl Iterator<Item = &'a str> + 'a {
+        self.cells.iter().map(String::as_str)
+    }
+
+    /**
+     * To Map
+     *
+     * Map of head to cell. Heads without a cell are left out.
+     */
+    pub fn to_map(&self) -> HashMap<&'a str, &'a str> {
+        self.heads
+            .iter()
+            .map(String::as_str)
+            .zip(self.iter())
+            .collect()
+    }
+}
+
+impl<'a> Index<usize> for RowRef<'a> {
+    type Output = str;
+
+    fn index(&self, position: usize) -> &str {
+        &self.cells[position]
+    }
+}
+
+impl<'a> Index<&str> for RowRef<'a> {
+    type Output = str;
+
+    fn index(&self, name: &str) -> &str {
+        match self.get(name) {
+            Some(cell) => cell,
+            None => panic!("no cell under head {name:?}"),
+        }
+    }
+}
+
+impl<'a> From<RowRef<'a>> for HashMap<&'a str, &'a str> {
+    fn from(row: RowRef<'a>) -> Self {
+        row.to_map()
+    }
+}
+
+/**
+ * Row Iter
+ *
+ * Iterator over the rows of a `CsvFile`.
+ */
+#[derive(Debug, Clone)]
+pub struct RowIter<'a> {
+    heads: &'a [String],
+    rows: Enumerate<slice::Iter<'a, CsvRow>>,
+}
+
+impl<'a> Iterator for RowIter<'a> {
+    type Item = RowRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, row) = self.rows.next()?;
+
+        Some(RowRef::new(self.heads, row, index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.rows.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for RowIter<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let (index, row) = self.rows.next_back()?;
+
+        Some(RowRef::new(self.heads, row, index))
+    }
+}
+
+impl ExactSizeIterator for RowIter<'_> {}
+
+impl CsvFile {
+    /**
+     * Iter
+     *
+     * Iterate over the rows.
+     */
+    pub fn iter(&self) -> RowIter<'_> {
+        RowIter {
+            heads: &self.heads,
+            rows: self.rows.iter().enumerate(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a CsvFile {
+    type Item = RowRef<'a>;
+    type IntoIter = RowIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShapePolicy;
+
+    fn sample() -> CsvFile {
+        let mut file = CsvFile::new();
+        file.set_shape_policy(ShapePolicy::Flexible);
+        for head in ["name", "age", "city"] {
+            file.push_head(head);
+        }
+        file.push_row(&["Ann", "30", "Oslo"]).unwrap();
+        file.push_row(&["Bob", "old"]).unwrap();
+        file
+    }
+
+    #[test]
+    fn get_by_name_and_position() {
+        let file = sample();
+        let row = file.row(1).unwrap();
+
+        assert_eq!(row.index(), 1);
+        assert_eq!(row.len(), 2);
+        assert_eq!(row.get("name"), Some("Bob"));
+        assert_eq!(row.get("city"), None);
+        assert_eq!(row.get("missing"), None);
+        assert_eq!(row.get_at(1), Some("old"));
+        assert_eq!(row.get_at(2), None);
+        assert_eq!(row.iter().collect::<Vec<_>>(), ["Bob", "old"]);
+    }
+
+    #[test]
+    fn get_parsed() {
+        let file = sample();
+
+        assert_eq!(file.row(0).unwrap().get_parsed::<u32>("age"), Some(Ok(30)));
+        assert!(matches!(
+            file.row(1).unwrap().get_parsed::<u32>("age"),
+            Some(Err(_))
+        ));
+        assert!(file.row(0).unwrap().get_parsed::<u32>("missing").is_none());
+    }
+
+    #[test]
+    fn index_by_name_and_position() {
+        let file = sample();
+        let row = file.row(0).unwrap();
+
+        assert_eq!(&row["city"], "Oslo");
+        assert_eq!(&row[1], "30");
+    }
+
+    #[test]
+    #[should_panic(expected = "no cell under head \"city\"")]
+    fn index_by_name_panics_without_a_cell() {
+        let file = sample();
+        let _ = &file.row(1).unwrap()["city"];
+    }
+
+    #[test]
+    fn to_map_leaves_out_missing_cells() {
+        let file = sample();
+
+        assert_eq!(
+            file.row(0).unwrap().to_map(),
+            HashMap::from([("name", "Ann"), ("age", "30"), ("city", "Oslo")])
+        );
+        assert_eq!(
+            HashMap::from(file.row(1).unwrap()),
+            HashMap::from([("name", "Bob"), ("age", "old")])
+        );
+    }
+
+    #[test]
+    fn iterates_both_ways() {
+        let file = sample();
+
+        assert_eq!(file.iter().len(), 2);
+        assert_eq!(
+            file.iter().rev().map(|row| row.index()).collect::<Vec<_>>(),
+            [1, 0]
+        );
+        assert_eq!(&(&file).into_iter().next().unwrap()["name"], "Ann");
+    }
+}
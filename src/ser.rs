@@ -0,0 +1,514 @@
+use std::error::Error;
+use std::fmt;
+
+use serde::ser::{self, Impossible, Serialize, Serializer};
+
+use crate::{CsvError, CsvFile};
+
+impl CsvFile {
+    /**
+     * Serialize
+     *
+     * Serialize records into a new CSV file. Heads come from the field names
+     * of the first record; tuples and sequences give a file without heads.
+     */
+    pub fn serialize<T: Serialize>(records: &[T]) -> Result<CsvFile, CsvError> {
+        let mut file = CsvFile::new();
+
+        for (index, record) in records.iter().enumerate() {
+            let error = |message: String| CsvError::Serialize {
+                row: index,
+                message,
+            };
+
+            let mut names = Vec::new();
+            let mut cells = Vec::new();
+            record
+                .serialize(RecordSerializer {
+                    names: &mut names,
+                    cells: &mut cells,
+                })
+                .map_err(|err| error(err.0))?;
+
+            if index == 0 {
+                file.heads = names;
+            } else if !names.is_empty() && names != file.heads {
+                return Err(error(format!(
+                    "fields {names:?} don't match heads {:?}",
+                    file.heads
+                )));
+            }
+
+            if !file.heads.is_empty() && cells.len() != file.heads.len() {
+                return Err(error(format!(
+                    "expected {} fields but found {}",
+                    file.heads.len(),
+                    cells.len()
+                )));
+            }
+
+            file.rows.push(cells);
+        }
+
+        Ok(file)
+    }
+}
+
+/**
+ * Ser Error
+ *
+ * Serialize error message.
+ */
+#[derive(Debug)]
+struct SerError(String);
+
+impl fmt::Display for SerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for SerError {}
+
+impl ser::Error for SerError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+fn unsupported<T>(kind: &str) -> Result<T, SerError> {
+    Err(SerError(format!("{kind} can't be written to a CSV cell")))
+}
+
+/**
+ * Record Serializer
+ *
+ * Collects a record's field names and cells.
+ */
+struct RecordSerializer<'a> {
+    names: &'a mut Vec<String>,
+    cells: &'a mut Vec<String>,
+}
+
+impl RecordSerializer<'_> {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        self.cells.push(value.serialize(CellSerializer)?);
+        Ok(())
+    }
+}
+
+macro_rules! serialize_cell {
+    ($($method:ident: $ty:ty,)*) => {
+        $(
+            fn $method(mut self, value: $ty) -> Result<(), SerError> {
+                self.push(&value)
+            }
+        )*
+    };
+}
+
+impl<'a> Serializer for RecordSerializer<'a> {
+    type Ok = ();
+    type Error = SerError;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Impossible<(), SerError>;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Impossible<(), SerError>;
+
+    serialize_cell! {
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_char: char,
+        serialize_str: &str,
+        serialize_bytes: &[u8],
+    }
+
+    fn serialize_none(mut self) -> Result<(), SerError> {
+        self.push(&())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<(), SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(mut self) -> Result<(), SerError> {
+        self.push(&())
+    }
+
+    fn serialize_unit_struct(mut self, _name: &'static str) -> Result<(), SerError> {
+        self.push(&())
+    }
+
+    fn serialize_unit_variant(
+        mut self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<(), SerError> {
+        self.push(variant)
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<(), SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<(), SerError> {
+        unsupported("newtype variant")
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self, SerError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self, SerError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, SerError> {
+        Ok(self)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerError> {
+        unsupported("tuple variant")
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self, SerError> {
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, SerError> {
+        Ok(self)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerError> {
+        unsupported("struct variant")
+    }
+}
+
+impl ser::SerializeSeq for RecordSerializer<'_> {
+    type Ok = ();
+    type Error = SerError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<(), SerError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for RecordSerializer<'_> {
+    type Ok = ();
+    type Error = SerError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<(), SerError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for RecordSerializer<'_> {
+    type Ok = ();
+    type Error = SerError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<(), SerError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for RecordSerializer<'_> {
+    type Ok = ();
+    type Error = SerError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerError> {
+        self.names.push(key.serialize(CellSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<(), SerError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for RecordSerializer<'_> {
+    type Ok = ();
+    type Error = SerError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), SerError> {
+        self.names.push(key.to_string());
+        self.push(value)
+    }
+
+    fn end(self) -> Result<(), SerError> {
+        Ok(())
+    }
+}
+
+/**
+ * Cell Serializer
+ *
+ * Serializes a single value into the text of one cell.
+ */
+struct CellSerializer;
+
+macro_rules! serialize_display {
+    ($($method:ident: $ty:ty,)*) => {
+        $(
+            fn $method(self, value: $ty) -> Result<String, SerError> {
+                Ok(value.to_string())
+            }
+        )*
+    };
+}
+
+impl Serializer for CellSerializer {
+    type Ok = String;
+    type Error = SerError;
+    type SerializeSeq = Impossible<String, SerError>;
+    type SerializeTuple = Impossible<String, SerError>;
+    type SerializeTupleStruct = Impossible<String, SerError>;
+    type SerializeTupleVariant = Impossible<String, SerError>;
+    type SerializeMap = Impossible<String, SerError>;
+    type SerializeStruct = Impossible<String, SerError>;
+    type SerializeStructVariant = Impossible<String, SerError>;
+
+    serialize_display! {
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_i128: i128,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_u128: u128,
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_char: char,
+        serialize_str: &str,
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<String, SerError> {
+        String::from_utf8(value.to_vec()).map_err(|_| SerError("bytes aren't valid UTF-8".into()))
+    }
+
+    fn serialize_none(self) -> Result<String, SerError> {
+        Ok(String::new())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<String, SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<String, SerError> {
+        Ok(String::new())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, SerError> {
+        Ok(String::new())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        variant: &'static str,
+    ) -> Result<String, SerError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<String, SerError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _value: &T,
+    ) -> Result<String, SerError> {
+        unsupported("newtype variant")
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, SerError> {
+        unsupported("nested sequence")
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, SerError> {
+        unsupported("nested tuple")
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct, SerError> {
+        unsupported("nested tuple struct")
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant, SerError> {
+        unsupported("tuple variant")
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, SerError> {
+        unsupported("nested map")
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStruct, SerError> {
+        unsupported("nested struct")
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant, SerError> {
+        unsupported("struct variant")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Serialize};
+
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+    enum Status {
+        Active,
+        Retired,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Person {
+        name: String,
+        age: Option<u32>,
+        score: f64,
+        status: Status,
+    }
+
+    #[test]
+    fn structs_round_trip() {
+        let people = vec![
+            Person {
+                name: "Ann, \"A\"".into(),
+                age: Some(30),
+                score: 1.5,
+                status: Status::Active,
+            },
+            Person {
+                name: "Bob".into(),
+                age: None,
+                score: -2.0,
+                status: Status::Retired,
+            },
+        ];
+
+        let file = CsvFile::serialize(&people).unwrap();
+        assert_eq!(file.heads(), &["name", "age", "score", "status"]);
+        assert_eq!(file.rows()[1], ["Bob", "", "-2", "Retired"]);
+
+        assert_eq!(file.deserialize::<Person>().unwrap(), people);
+    }
+
+    #[test]
+    fn tuples_have_no_heads() {
+        let file = CsvFile::serialize(&[("a", 1), ("b", 2)]).unwrap();
+
+        assert!(file.heads().is_empty());
+        assert_eq!(file.rows(), &[vec!["a", "1"], vec!["b", "2"]]);
+        assert_eq!(
+            file.deserialize::<(String, i32)>().unwrap(),
+            [("a".to_string(), 1), ("b".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    fn fields_must_match_heads() {
+        let records = [
+            BTreeMap::from([("a", "1"), ("b", "2")]),
+            BTreeMap::from([("a", "3"), ("c", "4")]),
+        ];
+
+        match CsvFile::serialize(&records) {
+            Err(CsvError::Serialize { row, message }) => {
+                assert_eq!(row, 1);
+                assert!(message.contains("don't match heads"), "{message}");
+            }
+            other => panic!("expected a Serialize error, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::CsvRow;
+
+/**
+ * Shape Policy
+ *
+ * What to do with rows that have more or fewer cells than the heads.
+ * Files without heads accept rows of any length.
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum ShapePolicy {
+    /// Reject rows of the wrong length.
+    #[default]
+    Strict,
+    /// Fill missing cells with the given value. Longer rows are rejected.
+    Pad(String),
+    /// Drop extra cells. Shorter rows are rejected.
+    Truncate,
+    /// Accept rows of any length.
+    Flexible,
+}
+
+impl ShapePolicy {
+    /**
+     * Fit
+     *
+     * Fit a row to `width` cells, or `None` when the policy rejects it.
+     */
+    pub(crate) fn fit(&self, width: usize, mut row: CsvRow) -> Option<CsvRow> {
+        if width == 0 || row.len() == width {
+            return Some(row);
+        }
+
+        match self {
+            Self::Strict => None,
+            Self::Pad(value) if row.len() < width => {
+                row.resize(width, value.clone());
+                Some(row)
+            }
+            Self::Truncate if row.len() > width => {
+                row.truncate(width);
+                Some(row)
+            }
+            Self::Pad(_) | Self::Truncate => None,
+            Self::Flexible => Some(row),
+        }
+    }
+
+    /**
+     * Fill
+     *
+     * Cell given to every row when a head is added, or `None` when rows
+     * aren't kept in line with the heads.
+     */
+    pub(crate) fn fill(&self) -> Option<String> {
+        match self {
+            Self::Pad(value) => Some(value.clone()),
+            Self::Strict | Self::Truncate => Some(String::new()),
+            Self::Flexible => None,
+        }
+    }
+}
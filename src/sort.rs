@@ -0,0 +1,491 @@
+use std::cmp::Ordering;
+
+use crate::{CsvError, CsvFile};
+
+/**
+ * Sort Order
+ *
+ * Direction of a sort key.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/**
+ * Compare
+ *
+ * How the cells of a sort key are compared.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Compare {
+    /// Byte-wise string order.
+    #[default]
+    Lexicographic,
+    /// Digit runs compare as numbers, so `file9` sorts before `file10`.
+    Natural,
+    /// Cells compare as decimal numbers. Cells that aren't numbers sort after
+    /// those that are, in either order.
+    Numeric,
+    /// Cells compare as ISO 8601 dates, e.g. `2024-01-31` or
+    /// `2024-01-31T10:30:00`. Cells that aren't dates sort after those that
+    /// are, in either order.
+    Date,
+    /// String order ignoring case.
+    CaseInsensitive,
+}
+
+/**
+ * Nulls
+ *
+ * Where empty and missing cells go, whatever the sort order.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Nulls {
+    First,
+    #[default]
+    Last,
+}
+
+/**
+ * Sort Key
+ *
+ * A column to sort rows by.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortKey {
+    pub column: String,
+    pub order: SortOrder,
+    pub compare: Compare,
+    pub nulls: Nulls,
+}
+
+impl SortKey {
+    /**
+     * Asc
+     *
+     * Ascending lexicographic key with nulls last.
+     */
+    pub fn asc(column: &str) -> Self {
+        Self {
+            column: column.to_string(),
+            order: SortOrder::Ascending,
+            compare: Compare::default(),
+            nulls: Nulls::default(),
+        }
+    }
+
+    /**
+     * Desc
+     *
+     * Descending lexicographic key with nulls last.
+     */
+    pub fn desc(column: &str) -> Self {
+        Self {
+            order: SortOrder::Descending,
+            ..Self::asc(column)
+        }
+    }
+
+    /**
+     * With Compare
+     *
+     * Set how cells are compared.
+     */
+    pub fn with_compare(mut self, compare: Compare) -> Self {
+        self.compare = compare;
+        self
+    }
+
+    /**
+     * With Nulls
+     *
+     * Set where empty and missing cells go.
+     */
+    pub fn with_nulls(mut self, nulls: Nulls) -> Self {
+        self.nulls = nulls;
+        self
+    }
+}
+
+/**
+ * Sort Value
+ *
+ * A cell prepared for comparison.
+ */
+#[derive(Debug)]
+enum SortValue<'a> {
+    Null,
+    Number(f64),
+    Date([u32; 7]),
+    Text(&'a str),
+    Folded(String),
+    /// Cell that didn't parse in numeric or date mode.
+    Invalid(&'a str),
+}
+
+impl<'a> SortValue<'a> {
+    fn new(cell: Option<&'a String>, compare: Compare) -> Self {
+        let cell = match cell {
+            Some(cell) if !cell.trim().is_empty() => cell.as_str(),
+            _ => return Self::Null,
+        };
+
+        match compare {
+            Compare::Lexicographic | Compare::Natural => Self::Text(cell),
+            Compare::CaseInsensitive => Self::Folded(cell.to_lowercase()),
+            Compare::Numeric => cell
+                .trim()
+                .parse()
+                .map_or(Self::Invalid(cell), Self::Number),
+            Compare::Date => parse_date(cell.trim()).map_or(Self::Invalid(cell), Self::Date),
+        }
+    }
+
+    fn cmp(&self, other: &Self, key: &SortKey) -> Ordering {
+        let ordering = match (self, other) {
+            (Self::Null, Self::Null) => return Ordering::Equal,
+            (Self::Null, _) => return null_ordering(key.nulls),
+            (_, Self::Null) => return null_ordering(key.nulls).reverse(),
+            (Self::Number(a), Self::Number(b)) => a.total_cmp(b),
+            (Self::Date(a), Self::Date(b)) => a.cmp(b),
+            (Self::Text(a), Self::Text(b)) if key.compare == Compare::Natural => natural_cmp(a, b),
+            (Self::Text(a), Self::Text(b)) | (Self::Invalid(a), Self::Invalid(b)) => a.cmp(b),
+            (Self::Folded(a), Self::Folded(b)) => a.cmp(b),
+            (Self::Invalid(_), _) => return Ordering::Greater,
+            (_, Self::Invalid(_)) => return Ordering::Less,
+            _ => Ordering::Equal,
+        };
+
+        match key.order {
+            SortOrder::Ascending => ordering,
+            SortOrder::Descending => ordering.reverse(),
+        }
+    }
+}
+
+fn null_ordering(nulls: Nulls) -> Ordering {
+    match nulls {
+        Nulls::First => Ordering::Less,
+        Nulls::Last => Ordering::Greater,
+    }
+}
+
+/**
+ * Natural Cmp
+ *
+ * Compare strings with digit runs compared as numbers.
+ */
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a_rest, mut b_rest) = (a, b);
+
+    loop {
+        let (a_chunk, a_next) = next_chunk(a_rest);
+        let (b_chunk, b_next) = next_chunk(b_rest);
+
+        let ordering = match (a_chunk, b_chunk) {
+            ("", "") => return a.cmp(b),
+            ("", _) => return Ordering::Less,
+            (_, "") => return Ordering::Greater,
+            (a_chunk, b_chunk) if is_digits(a_chunk) && is_digits(b_chunk) => {
+                let a_num = a_chunk.trim_start_matches('0');
+                let b_num = b_chunk.trim_start_matches('0');
+
+                a_num.len().cmp(&b_num.len()).then(a_num.cmp(b_num))
+            }
+            (a_chunk, b_chunk) => a_chunk.cmp(b_chunk),
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+
+        a_rest = a_next;
+        b_rest = b_next;
+    }
+}
+
+/**
+ * Next Chunk
+ *
+ * Split off the leading run of digits or non-digits.
+ */
+fn next_chunk(text: &str) -> (&str, &str) {
+    let digits = text.starts_with(|c: char| c.is_ascii_digit());
+    let end = text
+        .find(|c: char| c.is_ascii_digit() != digits)
+        .unwrap_or(text.len());
+
+    text.split_at(end)
+}
+
+fn is_digits(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_digit())
+}
+
+/**
+ * Parse Date
+ *
+ * Parse `YYYY-MM-DD` or `YYYY/MM/DD`, optionally followed by `T` or a space
+ * and `HH:MM[:SS[.fff]]`, into sortable parts. A trailing `Z` or offset is
+ * ignored.
+ */
+fn parse_date(text: &str) -> Option<[u32; 7]> {
+    let (date, time) = match text.find(['T', ' ']) {
+        Some(split) => (&text[..split], Some(text[split + 1..].trim())),
+        None => (text, None),
+    };
+
+    let mut parts = date.split(['-', '/']);
+    let year = parse_part(parts.next()?, 4, 4, 9999)?;
+    let month = parse_part(parts.next()?, 1, 2, 12)?;
+    let day = parse_part(parts.next()?, 1, 2, 31)?;
+    if parts.next().is_some() || month == 0 || day == 0 {
+        return None;
+    }
+
+    let mut parsed = [year, month, day, 0, 0, 0, 0];
+    let Some(time) = time else {
+        return Some(parsed);
+    };
+
+    let time = time
+        .trim_end_matches('Z')
+        .split(['+', '-'])
+        .next()
+        .unwrap_or_default();
+    let (time, fraction) = time.split_once('.').unwrap_or((time, ""));
+
+    let mut parts = time.split(':');
+    parsed[3] = parse_part(parts.next()?, 1, 2, 23)?;
+    parsed[4] = parse_part(parts.next()?, 1, 2, 59)?;
+    if let Some(second) = parts.next() {
+        parsed[5] = parse_part(second, 1, 2, 60)?;
+    }
+    if parts.next().is_some() {
+        return None;
+    }
+
+    if !fraction.is_empty() {
+        // Pad to nanoseconds so `.5` and `.50` compare equal.
+        let digits = fraction.get(..9).unwrap_or(fraction);
+        parsed[6] = parse_part(&format!("{digits:0<9}"), 9, 9, 999_999_999)?;
+    }
+
+    Some(parsed)
+}
+
+fn parse_part(text: &str, min_len: usize, max_len: usize, max: u32) -> Option<u32> {
+    if text.len() < min_len || text.len() > max_len || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    text.parse().ok().filter(|value| *value <= max)
+}
+
+impl CsvFile {
+    /**
+     * Sort By Columns
+     *
+     * Stable sort of the rows by each key in turn.
+     */
+    pub fn sort_by_columns(&mut self, keys: &[SortKey]) -> Result<(), CsvError> {
+        let columns = keys
+            .iter()
+            .map(|key| self.column_index(&key.column))
+            .collect::<Result<Vec<usize>, CsvError>>()?;
+
+        let mut order = self
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| {
+                let values = keys
+                    .iter()
+                    .zip(columns.iter())
+                    .map(|(key, &column)| SortValue::new(row.get(column), key.compare))
+                    .collect::<Vec<SortValue<'_>>>();
+
+                (index, values)
+            })
+            .collect::<Vec<_>>();
+
+        order.sort_by(|(_, a), (_, b)| {
+            keys.iter()
+                .zip(a.iter().zip(b.iter()))
+                .map(|(key, (a, b))| a.cmp(b, key))
+                .find(|ordering| *ordering != Ordering::Equal)
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let order = order
+            .into_iter()
+            .map(|(index, _)| index)
+            .collect::<Vec<usize>>();
+        let mut rows = std::mem::take(&mut self.rows)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        self.rows = order
+            .into_iter()
+            .filter_map(|index| rows[index].take())
+            .collect();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(cells: &[&str]) -> CsvFile {
+        let mut file = CsvFile::new();
+        file.push_head("v");
+        file.push_head("i");
+        for (index, cell) in cells.iter().enumerate() {
+            file.push_row(&[cell, &index.to_string()]).unwrap();
+        }
+        file
+    }
+
+    fn sorted(cells: &[&str], key: SortKey) -> Vec<String> {
+        let mut file = file(cells);
+        file.sort_by_columns(&[key]).unwrap();
+        file.col("v").unwrap().map(str::to_string).collect()
+    }
+
+    #[test]
+    fn lexicographic_and_case_insensitive() {
+        let cells = ["b", "B", "a", "file10", "file9"];
+
+        assert_eq!(
+            sorted(&cells, SortKey::asc("v")),
+            ["B", "a", "b", "file10", "file9"]
+        );
+        assert_eq!(
+            sorted(
+                &["b", "C", "a"],
+                SortKey::asc("v").with_compare(Compare::CaseInsensitive)
+            ),
+            ["a", "b", "C"]
+        );
+    }
+
+    #[test]
+    fn natural_order() {
+        let key = SortKey::asc("v").with_compare(Compare::Natural);
+
+        assert_eq!(
+            sorted(&["file10", "file9", "file009b", "file1", "x"], key.clone()),
+            ["file1", "file9", "file009b", "file10", "x"]
+        );
+        assert_eq!(sorted(&["a02", "a2"], key), ["a02", "a2"]);
+    }
+
+    #[test]
+    fn numeric_order_puts_invalid_cells_last() {
+        let cells = ["10", "abc", "-1.5", "9", "1e1"];
+        let key = SortKey::asc("v").with_compare(Compare::Numeric);
+
+        assert_eq!(sorted(&cells, key), ["-1.5", "9", "10", "1e1", "abc"]);
+        assert_eq!(
+            sorted(&cells, SortKey::desc("v").with_compare(Compare::Numeric)),
+            ["10", "1e1", "9", "-1.5", "abc"]
+        );
+    }
+
+    #[test]
+    fn date_order_puts_invalid_cells_last() {
+        let cells = [
+            "2024-02-01",
+            "2024-13-01",
+            "2023-12-31T23:59:59Z",
+            "2024/01/15 08:00",
+            "2024-01-15",
+        ];
+        let key = SortKey::asc("v").with_compare(Compare::Date);
+
+        assert_eq!(
+            sorted(&cells, key),
+            [
+                "2023-12-31T23:59:59Z",
+                "2024-01-15",
+                "2024/01/15 08:00",
+                "2024-02-01",
+                "2024-13-01",
+            ]
+        );
+        assert_eq!(
+            sorted(&cells, SortKey::desc("v").with_compare(Compare::Date)),
+            [
+                "2024-02-01",
+                "2024/01/15 08:00",
+                "2024-01-15",
+                "2023-12-31T23:59:59Z",
+                "2024-13-01",
+            ]
+        );
+    }
+
+    #[test]
+    fn nulls_ignore_the_order() {
+        let cells = ["b", "", "a", " "];
+
+        for (key, expected) in [
+            (SortKey::asc("v"), ["a", "b", "", " "]),
+            (SortKey::desc("v"), ["b", "a", "", " "]),
+            (
+                SortKey::asc("v").with_nulls(Nulls::First),
+                ["", " ", "a", "b"],
+            ),
+            (
+                SortKey::desc("v").with_nulls(Nulls::First),
+                ["", " ", "b", "a"],
+            ),
+        ] {
+            assert_eq!(sorted(&cells, key.clone()), expected, "{key:?}");
+        }
+    }
+
+    #[test]
+    fn sort_is_stable_across_keys() {
+        let mut file = CsvFile::new();
+        for head in ["group", "n", "id"] {
+            file.push_head(head);
+        }
+        for row in [
+            ["b", "2", "0"],
+            ["a", "2", "1"],
+            ["b", "1", "2"],
+            ["a", "2", "3"],
+            ["b", "2", "4"],
+        ] {
+            file.push_row(&row).unwrap();
+        }
+
+        file.sort_by_columns(&[
+            SortKey::asc("group"),
+            SortKey::desc("n").with_compare(Compare::Numeric),
+        ])
+        .unwrap();
+
+        assert_eq!(
+            file.col("id").unwrap().collect::<Vec<_>>(),
+            ["1", "3", "0", "4", "2"]
+        );
+    }
+
+    #[test]
+    fn unknown_columns_fail() {
+        let mut file = file(&["a"]);
+
+        assert!(matches!(
+            file.sort_by_columns(&[SortKey::asc("missing")]),
+            Err(CsvError::UnknownColumn(_))
+        ));
+    }
+}
@@ -0,0 +1,392 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+use crate::{CsvError, Dialect, Escape};
+
+/**
+ * Quote Style
+ *
+ * When the writer wraps a cell in quotes.
+ */
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// Quote only cells that contain a separator, quote or line break.
+    #[default]
+    Minimal,
+    /// Quote every cell.
+    Always,
+    /// Quote every cell that isn't a number.
+    NonNumeric,
+    /// Never quote. Cells with special characters are written raw.
+    Never,
+}
+
+/**
+ * Write Options
+ *
+ * Options for writing CSV files.
+ */
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteOptions {
+    pub dialect: Dialect,
+    pub quote_style: QuoteStyle,
+    /// Write the header record. Otherwise `write_header` writes nothing.
+    pub has_headers: bool,
+}
+
+impl Default for WriteOptions {
+    fn default() -> Self {
+        Self {
+            dialect: Dialect::default(),
+            quote_style: QuoteStyle::default(),
+            has_headers: true,
+        }
+    }
+}
+
+/**
+ * CSV Writer
+ *
+ * Streaming CSV writer over any `Write`. Records are written as they come,
+ * with the same quoting and dialect rules as `CsvFile::write`.
+ */
+#[derive(Debug)]
+pub struct CsvWriter<W: Write> {
+    inner: BufWriter<W>,
+    options: WriteOptions,
+    /// Reused line buffer.
+    line: String,
+}
+
+impl CsvWriter<File> {
+    /**
+     * From Path
+     *
+     * Create a CSV file for writing.
+     */
+    pub fn from_path<P: AsRef<Path>>(path: P, options: WriteOptions) -> Result<Self, CsvError> {
+        Ok(Self::with_options(File::create(path)?, options))
+    }
+}
+
+impl<W: Write> CsvWriter<W> {
+    /**
+     * New
+     *
+     * New writer with the default options.
+     */
+    pub fn new(writer: W) -> Self {
+        Self::with_options(writer, WriteOptions::default())
+    }
+
+    /**
+     * With Options
+     *
+     * New writer with the given options.
+     */
+    pub fn with_options(writer: W, options: WriteOptions) -> Self {
+        Self {
+            inner: BufWriter::new(writer),
+            options,
+            line: String::new(),
+        }
+    }
+
+    /**
+     * Write Header
+     *
+     * Write the header record, unless `has_headers` is off.
+     */
+    pub fn write_header<S: AsRef<str>>(&mut self, heads: &[S]) -> Result<(), CsvError> {
+        if !self.options.has_headers {
+            return Ok(());
+        }
+
+        self.write_record(heads)
+    }
+
+    /**
+     * Write Record
+     *
+     * Write one record, quoting cells as needed.
+     */
+    pub fn write_record<S: AsRef<str>>(&mut self, record: &[S]) -> Result<(), CsvError> {
+        self.line.clear();
+        format_row(&mut self.line, record, &self.options);
+        self.line.push_str(self.options.dialect.terminator.as_str());
+
+        self.inner.write_all(self.line.as_bytes())?;
+        Ok(())
+    }
+
+    /**
+     * Flush
+     *
+     * Flush buffered records to the underlying writer.
+     */
+    pub fn flush(&mut self) -> Result<(), CsvError> {
+        self.inner.flush()?;
+        Ok(())
+    }
+
+    /**
+     * Into Inner
+     *
+     * Flush and return the underlying writer.
+     */
+    pub fn into_inner(self) -> Result<W, CsvError> {
+        self.inner
+            .into_inner()
+            .map_err(|err| CsvError::Io(err.into_error()))
+    }
+}
+
+/**
+ * Format Row
+ *
+ * Push a row as a CSV line, without the line terminator.
+ */
+fn format_row<S: AsRef<str>>(line: &mut String, row: &[S], options: &WriteOptions) {
+    let dialect = &options.dialect;
+
+    // A lone empty cell would be written as a blank line, which reads back as nothing.
+    if let ([cell], Some(quote)) = (row, dialect.quote) {
+        if cell.as_ref().is_empty() && options.quote_style != QuoteStyle::Never {
+            line.push(quote);
+            line.push(quote);
+            return;
+        }
+    }
+
+    for (index, cell) in row.iter().enumerate() {
+        if index > 0 {
+            line.push_str(&dialect.delimiter);
+        }
+
+        format_cell(line, cell.as_ref(), options);
+    }
+}
+
+/**
+ * Format Cell
+ *
+ * Push a single cell, quoting and escaping it when needed.
+ */
+fn format_cell(line: &mut String, cell: &str, options: &WriteOptions) {
+    let dialect = &options.dialect;
+    let escape = match dialect.escape {
+        Escape::Double => None,
+        Escape::Char(escape) => Some(escape),
+    };
+
+    let quote = match options.quote_style {
+        QuoteStyle::Minimal => needs_quotes(cell, dialect),
+        QuoteStyle::Always => true,
+        QuoteStyle::NonNumeric => !is_numeric(cell),
+        QuoteStyle::Never => false,
+    };
+
+    let quote = match (quote, dialect.quote) {
+        (true, Some(quote)) => quote,
+        // Without quotes, an escape char can still protect special characters.
+        (_, None) if options.quote_style != QuoteStyle::Never && escape.is_some() => {
+            escape_unquoted(line, cell, dialect);
+            return;
+        }
+        _ => {
+            line.push_str(cell);
+            return;
+        }
+    };
+
+    line.push(quote);
+    for c in cell.chars() {
+        match escape {
+            Some(escape) if c == quote || c == escape => line.push(escape),
+            None if c == quote => line.push(quote),
+            _ => {}
+        }
+        line.push(c);
+    }
+    line.push(quote);
+}
+
+/**
+ * Escape Unquoted
+ *
+ * Push a cell with special characters prefixed by the escape char.
+ */
+fn escape_unquoted(line: &mut String, cell: &str, dialect: &Dialect) {
+    let Escape::Char(escape) = dialect.escape else {
+        line.push_str(cell);
+        return;
+    };
+
+    let mut rest = cell;
+    while let Some(c) = rest.chars().next() {
+        if rest.starts_with(dialect.delimiter.as_str()) && !dialect.delimiter.is_empty() {
+            for c in dialect.delimiter.chars() {
+                line.push(escape);
+                line.push(c);
+            }
+            rest = &rest[dialect.delimiter.len()..];
+            continue;
+        }
+
+        if matches!(c, '\r' | '\n') || c == escape {
+            line.push(escape);
+        }
+        line.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+}
+
+/**
+ * Needs Quotes
+ *
+ * Whether a cell must be quoted to be read back unchanged.
+ */
+fn needs_quotes(cell: &str, dialect: &Dialect) -> bool {
+    if !dialect.delimiter.is_empty() && cell.contains(dialect.delimiter.as_str()) {
+        return true;
+    }
+
+    if dialect.trim && cell.trim_matches(|c| c == ' ' || c == '\t').len() != cell.len() {
+        return true;
+    }
+
+    cell.chars().any(|c| {
+        matches!(c, '\r' | '\n') || Some(c) == dialect.quote || dialect.escape == Escape::Char(c)
+    })
+}
+
+/**
+ * Is Numeric
+ *
+ * Whether a cell looks like a plain decimal number, e.g. `-12`, `3.5` or `1e6`.
+ */
+pub(crate) fn is_numeric(cell: &str) -> bool {
+    let bytes = cell.as_bytes();
+    let mut i = 0;
+
+    if matches!(bytes.first(), Some(b'+' | b'-')) {
+        i += 1;
+    }
+
+    let int_start = i;
+    while i < bytes.len() && bytes[i].is_ascii_digit() {
+        i += 1;
+    }
+    let mut digits = i - int_start;
+
+    if i < bytes.len() && bytes[i] == b'.' {
+        i += 1;
+        let frac_start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        digits += i - frac_start;
+    }
+
+    if digits == 0 {
+        return false;
+    }
+
+    if i < bytes.len() && matches!(bytes[i], b'e' | b'E') {
+        i += 1;
+        if matches!(bytes.get(i), Some(b'+' | b'-')) {
+            i += 1;
+        }
+        let exp_start = i;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == exp_start {
+            return false;
+        }
+    }
+
+    i == bytes.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Terminator;
+
+    fn write(options: WriteOptions, records: &[&[&str]]) -> String {
+        let mut writer = CsvWriter::with_options(Vec::new(), options);
+        writer.write_header(&["name", "note"]).unwrap();
+        for record in records {
+            writer.write_record(record).unwrap();
+        }
+
+        String::from_utf8(writer.into_inner().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn writes_header_and_records() {
+        let csv = write(
+            WriteOptions::default(),
+            &[&["Ann", "a,b"], &["Bob", "say \"hi\"\nbye"], &[""]],
+        );
+
+        assert_eq!(
+            csv,
+            "name,note\nAnn,\"a,b\"\nBob,\"say \"\"hi\"\"\nbye\"\n\"\"\n"
+        );
+    }
+
+    #[test]
+    fn header_is_skipped_without_headers() {
+        let options = WriteOptions {
+            has_headers: false,
+            ..WriteOptions::default()
+        };
+
+        assert_eq!(write(options, &[&["Ann", "1"]]), "Ann,1\n");
+    }
+
+    #[test]
+    fn quote_styles() {
+        let record: &[&str] = &["a", "1.5", "", "x;y"];
+        let cases = [
+            (QuoteStyle::Minimal, "a,1.5,,x;y\n"),
+            (QuoteStyle::Always, "\"a\",\"1.5\",\"\",\"x;y\"\n"),
+            (QuoteStyle::NonNumeric, "\"a\",1.5,\"\",\"x;y\"\n"),
+            (QuoteStyle::Never, "a,1.5,,x;y\n"),
+        ];
+
+        for (quote_style, expected) in cases {
+            let mut writer = CsvWriter::with_options(
+                Vec::new(),
+                WriteOptions {
+                    quote_style,
+                    ..WriteOptions::default()
+                },
+            );
+            writer.write_record(record).unwrap();
+
+            let csv = String::from_utf8(writer.into_inner().unwrap()).unwrap();
+            assert_eq!(csv, expected, "{quote_style:?}");
+        }
+    }
+
+    #[test]
+    fn dialect_sets_delimiter_escape_and_terminator() {
+        let options = WriteOptions {
+            dialect: Dialect {
+                escape: Escape::Char('\\'),
+                terminator: Terminator::CrLf,
+                ..Dialect::semicolon()
+            },
+            ..WriteOptions::default()
+        };
+
+        assert_eq!(
+            write(options, &[&["a;b", "say \"hi\""]]),
+            "name;note\r\n\"a;b\";\"say \\\"hi\\\"\"\r\n"
+        );
+    }
+}